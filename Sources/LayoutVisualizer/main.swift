@@ -121,6 +121,10 @@ func main() {
     render(layout: rotatedTwoPane, to: "rotated_two_pane.png")
     render(layout: reflectedTwoPane, to: "reflected_two_pane.png")
     render(layout: reflectedRotatedTwoPane, to: "reflected_rotated_two_pane.png")
+    render(layout: Spacing(layout: tiled, inner: 16, outer: 8, smartGaps: true), to: "spacing_tiled.png")
+    render(layout: Grid<View, ()>(), to: "grid.png")
+    render(layout: ThreeColumn<View, ()>(split: 0.5, delta: 0.1, centered: true), to: "three_column.png")
+    render(layout: Spiral<View, ()>(ratio: 0.6), to: "spiral.png")
 }
 
 
@@ -47,6 +47,34 @@ private class RotatedMapper<View, OutputData>: AnyLayoutMapper {
     }
 }
 
+private class SpacingMapper<View, OutputData>: AnyLayoutMapper {
+    private let inner: Int32
+    private let outer: Int32
+    private let smartGaps: Bool
+
+    init(inner: Int32, outer: Int32, smartGaps: Bool) {
+        self.inner = inner
+        self.outer = outer
+        self.smartGaps = smartGaps
+    }
+
+    func flatMap<L: Layout>(_ layout: L) -> AnyLayout<L.View, L.OutputData> {
+        AnyLayout.wrap(Spacing(layout: layout, inner: self.inner, outer: self.outer, smartGaps: self.smartGaps))
+    }
+}
+
+private class NamedMapper<View, OutputData>: AnyLayoutMapper {
+    private let name: String
+
+    init(name: String) {
+        self.name = name
+    }
+
+    func flatMap<L: Layout>(_ layout: L) -> AnyLayout<L.View, L.OutputData> {
+        AnyLayout.wrap(Named(layout: layout, name: self.name))
+    }
+}
+
 private class ChooseLayoutMapper<View, OutputData>: AnyLayoutMapper {
     private let right: AnyLayout<View, OutputData>
 
@@ -118,6 +146,42 @@ public func buildLayout<View: Equatable, OutputData>(
         case AwcLayoutOp_Tiled:
             let tiled = currentOp.pointee.tiled
             layouts.append(AnyLayout.wrap(Tiled(split: tiled.split, delta: tiled.delta)))
+        case AwcLayoutOp_Spacing:
+            guard let layout = layouts.popLast() else {
+                throw ConfigError.invalidLayout
+            }
+            let spacing = currentOp.pointee.spacing
+            layouts.append(layout.flatMap(SpacingMapper(
+                inner: Int32(clamping: spacing.inner),
+                outer: Int32(clamping: spacing.outer),
+                smartGaps: spacing.smart_gaps)))
+        case AwcLayoutOp_Grid:
+            let grid = currentOp.pointee.grid
+            let shape: GridShape
+            switch grid.tag {
+            case AwcGrid_Columns:
+                shape = .columns(Int(grid.columns))
+            case AwcGrid_AspectRatio:
+                shape = .aspectRatio(grid.aspect_ratio)
+            default:
+                shape = .auto
+            }
+            layouts.append(AnyLayout.wrap(Grid(shape: shape)))
+        case AwcLayoutOp_ThreeColumn:
+            let threeColumn = currentOp.pointee.three_column
+            layouts.append(AnyLayout.wrap(ThreeColumn(
+                split: threeColumn.split,
+                delta: threeColumn.delta,
+                centered: threeColumn.centered)))
+        case AwcLayoutOp_Spiral:
+            layouts.append(AnyLayout.wrap(Spiral(ratio: currentOp.pointee.spiral.ratio)))
+        case AwcLayoutOp_Tabbed:
+            layouts.append(AnyLayout.wrap(Tabbed()))
+        case AwcLayoutOp_Named:
+            guard let layout = layouts.popLast() else {
+                throw ConfigError.invalidLayout
+            }
+            layouts.append(layout.flatMap(NamedMapper(name: String(cString: currentOp.pointee.named))))
         default:
             throw ConfigError.invalidLayout
         }
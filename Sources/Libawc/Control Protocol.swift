@@ -8,10 +8,12 @@ public enum CtlRequest: Decodable, Equatable {
     case newWorkspace(String)
     case renameWorkspace(String, String)
     case setLayout(UInt8)
+    case setLayoutByName(String)
 
     private enum Keys: String, CodingKey {
         case cmd
         case layoutNumber = "layout_number"
+        case name
         case newTag = "new_tag"
         case tag
     }
@@ -31,8 +33,12 @@ public enum CtlRequest: Decodable, Equatable {
             let newTag = try container.decode(String.self, forKey: .newTag)
             self = .renameWorkspace(tag, newTag)
         case "set_layout":
-            let layout = try container.decode(UInt8.self, forKey: .layoutNumber)
-            self = .setLayout(layout)
+            if let name = try container.decodeIfPresent(String.self, forKey: .name) {
+                self = .setLayoutByName(name)
+            } else {
+                let layout = try container.decode(UInt8.self, forKey: .layoutNumber)
+                self = .setLayout(layout)
+            }
         default:
             throw DecodingError.dataCorrupted(
                 DecodingError.Context(
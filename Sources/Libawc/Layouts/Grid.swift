@@ -0,0 +1,79 @@
+import Wlroots
+
+/// How the number of columns of a grid is chosen.
+public enum GridShape {
+    /// As many columns as rows, or one more
+    case auto
+    /// A fixed number of columns (fewer if there are fewer views)
+    case columns(Int)
+    /// As many columns as needed so that the cells come close to the given width/height ratio
+    case aspectRatio(Double)
+}
+
+/// Arranges all views in a grid of equally sized cells. If the views don't fill the last row,
+/// the views in that row share its width.
+public final class Grid<View: Equatable, OutputData>: Layout {
+    public let description: String = "Grid"
+
+    private let shape: GridShape
+
+    public init(shape: GridShape = .auto) {
+        self.shape = shape
+    }
+
+    public func emptyLayout<M: Layout>(
+        dataProvider: ExtensionDataProvider,
+        output: Output<M>,
+        box: wlr_box
+    ) -> [(View, Set<ViewAttribute>, wlr_box)] {
+        []
+    }
+
+    public func doLayout<L: Layout>(
+        dataProvider: ExtensionDataProvider,
+        output: Output<L>,
+        stack: Stack<View>,
+        box: wlr_box
+    ) -> [(View, Set<ViewAttribute>, wlr_box)] {
+        let views = stack.toArray()
+        let columns = self.columns(n: views.count, box: box)
+        let rows = (views.count + columns - 1) / columns
+
+        let boxes = splitVertically(n: rows, box: box).enumerated().flatMap { (row, rowBox) -> [wlr_box] in
+            let viewsInRow = min(columns, views.count - row * columns)
+            return splitHorizontally(n: viewsInRow, box: rowBox)
+        }
+        return zip(views, boxes).map { (view, box) in
+            (view, view == stack.focus ? [.focused] : [], box)
+        }
+    }
+
+    private func columns(n: Int, box: wlr_box) -> Int {
+        switch self.shape {
+        case .auto:
+            return Int(ceil(sqrt(Double(n))))
+        case .columns(let columns):
+            return max(1, min(columns, n))
+        case .aspectRatio(let ratio):
+            // With c columns and n / c rows, a cell's ratio is (W / c) / (H * c / n)
+            let columns = sqrt(Double(box.width) * Double(n) / (Double(box.height) * ratio))
+            guard columns.isFinite else {
+                return 1
+            }
+            return max(1, min(n, Int(columns.rounded())))
+        }
+    }
+}
+
+private func splitHorizontally(n: Int, box: wlr_box) -> [wlr_box] {
+    var boxes: [wlr_box] = []
+    var remainingWidth = box.width
+    var currentX = box.x
+    for i in (1...Int32(n)).reversed() {
+        let nextWidth = remainingWidth / i
+        boxes.append(wlr_box(x: currentX, y: box.y, width: nextWidth, height: box.height))
+        currentX += nextWidth
+        remainingWidth -= nextWidth
+    }
+    return boxes
+}
@@ -6,6 +6,8 @@ public struct ViewAttribute: Hashable {
     public static let focused: ViewAttribute = "focused"
     public static let floating: ViewAttribute = "floating"
     public static let undecorated: ViewAttribute = "undecorated"
+    /// The view is displayed below a strip of tabs for all views of the stack
+    public static let tabbed: ViewAttribute = "tabbed"
 }
 
 extension ViewAttribute: ExpressibleByStringLiteral {
@@ -0,0 +1,53 @@
+import Wlroots
+
+/// Gives another layout a custom description, e.g. to tell layouts apart in the layout switcher.
+/// Layouts that wrap it (such as `Spacing`) report their own description, so it must be the
+/// outermost layout of an alternative to be selected by name.
+public final class Named<WrappedLayout: Layout>: Layout {
+    public typealias View = WrappedLayout.View
+    public typealias OutputData = WrappedLayout.OutputData
+
+    public let description: String
+
+    private let layout: WrappedLayout
+
+    public init(layout: WrappedLayout, name: String) {
+        self.layout = layout
+        self.description = name
+    }
+
+    public func emptyLayout<L: Layout>(
+        dataProvider: ExtensionDataProvider,
+        output: Output<L>,
+        box: wlr_box
+    ) -> [(L.View, Set<ViewAttribute>, wlr_box)]
+    where WrappedLayout.View == L.View, WrappedLayout.OutputData == L.OutputData {
+        self.layout.emptyLayout(dataProvider: dataProvider, output: output, box: box)
+    }
+
+    public func doLayout<L: Layout>(
+        dataProvider: ExtensionDataProvider,
+        output: Output<L>,
+        stack: Stack<L.View>,
+        box: wlr_box
+    ) -> [(L.View, Set<ViewAttribute>, wlr_box)]
+    where WrappedLayout.View == L.View, WrappedLayout.OutputData == L.OutputData {
+        self.layout.doLayout(dataProvider: dataProvider, output: output, stack: stack, box: box)
+    }
+
+    public func firstLayout() -> Named<WrappedLayout> {
+        Named(layout: self.layout.firstLayout(), name: self.description)
+    }
+
+    public func nextLayout() -> Named<WrappedLayout>? {
+        self.layout.nextLayout().map { Named(layout: $0, name: self.description) }
+    }
+
+    public func expand() -> Named<WrappedLayout> {
+        Named(layout: self.layout.expand(), name: self.description)
+    }
+
+    public func shrink() -> Named<WrappedLayout> {
+        Named(layout: self.layout.shrink(), name: self.description)
+    }
+}
@@ -0,0 +1,91 @@
+import Wlroots
+
+/// Puts gaps between the views of another layout and between the views and the edges of the output.
+public final class Spacing<WrappedLayout: Layout>: Layout {
+    public typealias View = WrappedLayout.View
+    public typealias OutputData = WrappedLayout.OutputData
+
+    public var description: String {
+        get {
+            "Spacing(\(self.layout.description), \(self.inner), \(self.outer))"
+        }
+    }
+
+    private let layout: WrappedLayout
+    /// Space between two views
+    private let inner: Int32
+    /// Space between views and the edges
+    private let outer: Int32
+    /// Whether to omit the gaps if only one view is visible
+    private let smartGaps: Bool
+
+    public init(layout: WrappedLayout, inner: Int32, outer: Int32, smartGaps: Bool) {
+        self.layout = layout
+        self.inner = inner
+        self.outer = outer
+        self.smartGaps = smartGaps
+    }
+
+    public func emptyLayout<L: Layout>(
+        dataProvider: ExtensionDataProvider,
+        output: Output<L>,
+        box: wlr_box
+    ) -> [(L.View, Set<ViewAttribute>, wlr_box)]
+    where WrappedLayout.View == L.View, WrappedLayout.OutputData == L.OutputData {
+        self.layout.emptyLayout(dataProvider: dataProvider, output: output, box: box)
+    }
+
+    public func doLayout<L: Layout>(
+        dataProvider: ExtensionDataProvider,
+        output: Output<L>,
+        stack: Stack<L.View>,
+        box: wlr_box
+    ) -> [(L.View, Set<ViewAttribute>, wlr_box)]
+    where WrappedLayout.View == L.View, WrappedLayout.OutputData == L.OutputData {
+        // Each view gives up half of the inner gap on every side. The layout's box is enlarged by
+        // that much, so that the gaps at the edges are exactly `outer`.
+        let before = self.inner / 2
+        let after = self.inner - before
+        let arrangement = self.layout
+            .doLayout(
+                dataProvider: dataProvider,
+                output: output,
+                stack: stack,
+                box: box.inset(by: (self.outer - before, self.outer - after)))
+            .map { (view, attributes, viewBox) in (view, attributes, viewBox.inset(by: (before, after))) }
+
+        if self.smartGaps && arrangement.count == 1 {
+            return self.layout.doLayout(dataProvider: dataProvider, output: output, stack: stack, box: box)
+        }
+        return arrangement
+    }
+
+    public func firstLayout() -> Spacing<WrappedLayout> {
+        Spacing(layout: self.layout.firstLayout(), inner: self.inner, outer: self.outer, smartGaps: self.smartGaps)
+    }
+
+    public func nextLayout() -> Spacing<WrappedLayout>? {
+        self.layout.nextLayout().map {
+            Spacing(layout: $0, inner: self.inner, outer: self.outer, smartGaps: self.smartGaps)
+        }
+    }
+
+    public func expand() -> Spacing<WrappedLayout> {
+        Spacing(layout: self.layout.expand(), inner: self.inner, outer: self.outer, smartGaps: self.smartGaps)
+    }
+
+    public func shrink() -> Spacing<WrappedLayout> {
+        Spacing(layout: self.layout.shrink(), inner: self.inner, outer: self.outer, smartGaps: self.smartGaps)
+    }
+}
+
+private extension wlr_box {
+    /// Shrinks the box by `before` at the left and top and by `after` at the right and bottom.
+    func inset(by amount: (before: Int32, after: Int32)) -> wlr_box {
+        wlr_box(
+            x: self.x + amount.before,
+            y: self.y + amount.before,
+            width: max(1, self.width - amount.before - amount.after),
+            height: max(1, self.height - amount.before - amount.after))
+    }
+}
@@ -0,0 +1,74 @@
+import Wlroots
+
+/// Gives each view `ratio` of the space that is left by the views before it. The views are
+/// placed on the left, top, right and bottom of the remaining space in turn, so that they form a
+/// spiral. The last view takes all remaining space.
+public final class Spiral<View: Equatable, OutputData>: Layout {
+    public let description: String = "Spiral"
+
+    private let ratio: Double
+
+    public init(ratio: Double) {
+        self.ratio = ratio
+    }
+
+    public func emptyLayout<M: Layout>(
+        dataProvider: ExtensionDataProvider,
+        output: Output<M>,
+        box: wlr_box
+    ) -> [(View, Set<ViewAttribute>, wlr_box)] {
+        []
+    }
+
+    public func doLayout<L: Layout>(
+        dataProvider: ExtensionDataProvider,
+        output: Output<L>,
+        stack: Stack<View>,
+        box: wlr_box
+    ) -> [(View, Set<ViewAttribute>, wlr_box)] {
+        let views = stack.toArray()
+        var remaining = box
+        var boxes: [wlr_box] = []
+        for i in 0..<(views.count - 1) {
+            let (taken, rest) = self.split(box: remaining, side: i % 4)
+            boxes.append(taken)
+            remaining = rest
+        }
+        boxes.append(remaining)
+
+        return zip(views, boxes).map { (view, box) in
+            (view, view == stack.focus ? [.focused] : [], box)
+        }
+    }
+
+    /// Splits off `ratio` of the box on the left (0), top (1), right (2) or bottom (3) side and
+    /// returns that part and the rest.
+    private func split(box: wlr_box, side: Int) -> (wlr_box, wlr_box) {
+        switch side {
+        case 0:
+            let width = Int32(floor(Double(box.width) * self.ratio))
+            return (
+                wlr_box(x: box.x, y: box.y, width: width, height: box.height),
+                wlr_box(x: box.x + width, y: box.y, width: box.width - width, height: box.height)
+            )
+        case 1:
+            let height = Int32(floor(Double(box.height) * self.ratio))
+            return (
+                wlr_box(x: box.x, y: box.y, width: box.width, height: height),
+                wlr_box(x: box.x, y: box.y + height, width: box.width, height: box.height - height)
+            )
+        case 2:
+            let width = Int32(floor(Double(box.width) * self.ratio))
+            return (
+                wlr_box(x: box.x + box.width - width, y: box.y, width: width, height: box.height),
+                wlr_box(x: box.x, y: box.y, width: box.width - width, height: box.height)
+            )
+        default:
+            let height = Int32(floor(Double(box.height) * self.ratio))
+            return (
+                wlr_box(x: box.x, y: box.y + box.height - height, width: box.width, height: height),
+                wlr_box(x: box.x, y: box.y, width: box.width, height: box.height - height)
+            )
+        }
+    }
+}
@@ -0,0 +1,19 @@
+import Wlroots
+
+/// Like `Full`, only the focused view is displayed. The view is marked as `tabbed`, so that the
+/// compositor reserves space above it for a strip with a tab for every view of the stack.
+public final class Tabbed<View, OutputData>: Layout {
+    public let description: String = "Tabbed"
+
+    public init() {
+    }
+
+    public func doLayout<L: Layout>(
+        dataProvider: ExtensionDataProvider,
+        output: Output<L>,
+        stack: Stack<L.View>,
+        box: wlr_box
+    ) -> [(L.View, Set<ViewAttribute>, wlr_box)] where L.View == View {
+        [(stack.focus, [.focused, .tabbed], box)]
+    }
+}
@@ -0,0 +1,88 @@
+import Wlroots
+
+/// Splits the output into three columns: the main view takes `split` of the width and the
+/// remaining views are stacked in the two other columns. If `centered` is set, the main view is
+/// displayed in the middle column, otherwise in the left one. With only two views, the layout
+/// behaves like `Tiled`.
+public final class ThreeColumn<View: Equatable, OutputData>: Layout {
+    public let description: String = "ThreeColumn"
+
+    private let split: Double
+    private let delta: Double
+    private let centered: Bool
+
+    public init(split: Double, delta: Double, centered: Bool) {
+        self.split = split
+        self.delta = delta
+        self.centered = centered
+    }
+
+    public func emptyLayout<M: Layout>(
+        dataProvider: ExtensionDataProvider,
+        output: Output<M>,
+        box: wlr_box
+    ) -> [(View, Set<ViewAttribute>, wlr_box)] {
+        []
+    }
+
+    public func doLayout<L: Layout>(
+        dataProvider: ExtensionDataProvider,
+        output: Output<L>,
+        stack: Stack<View>,
+        box: wlr_box
+    ) -> [(View, Set<ViewAttribute>, wlr_box)] {
+        let views = stack.toArray()
+        guard views.count > 1 else {
+            return [(views.first!, [.focused], box)]
+        }
+
+        let boxes: [wlr_box]
+        if views.count == 2 {
+            let (left, right) = splitHorizontally(by: self.split, box: box)
+            boxes = [left, right]
+        } else {
+            // The column next to the main view gets the extra view if the number is odd
+            let secondary = views.count - 1
+            let first = (secondary + 1) / 2
+            let (main, firstColumn, secondColumn) = self.columns(box: box)
+            boxes = [main]
+                + splitVertically(n: first, box: firstColumn)
+                + splitVertically(n: secondary - first, box: secondColumn)
+        }
+        return zip(views, boxes).map { (view, box) in
+            (view, view == stack.focus ? [.focused] : [], box)
+        }
+    }
+
+    public func expand() -> ThreeColumn<View, OutputData> {
+        ThreeColumn(split: min(1, self.split + self.delta), delta: self.delta, centered: self.centered)
+    }
+
+    public func shrink() -> ThreeColumn<View, OutputData> {
+        ThreeColumn(split: max(0, self.split - self.delta), delta: self.delta, centered: self.centered)
+    }
+
+    /// Returns the boxes of the main column, the column next to it and the remaining column.
+    private func columns(box: wlr_box) -> (wlr_box, wlr_box, wlr_box) {
+        let mainWidth = Int32(floor(Double(box.width) * self.split))
+        let firstWidth = (box.width - mainWidth) / 2
+        let secondWidth = box.width - mainWidth - firstWidth
+        let column = { (x: Int32, width: Int32) in
+            wlr_box(x: box.x + x, y: box.y, width: width, height: box.height)
+        }
+
+        if self.centered {
+            return (
+                column(secondWidth, mainWidth),
+                column(secondWidth + mainWidth, firstWidth),
+                column(0, secondWidth)
+            )
+        } else {
+            return (
+                column(0, mainWidth),
+                column(mainWidth, firstWidth),
+                column(mainWidth + firstWidth, secondWidth)
+            )
+        }
+    }
+}
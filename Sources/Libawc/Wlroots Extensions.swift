@@ -208,4 +208,29 @@ public extension UnsafeMutablePointer where Pointee == wlr_output {
 #endif
         }
     }
+
+    var make: String {
+        get {
+            toString(array: self.pointee.make)
+        }
+    }
+
+    var model: String {
+        get {
+            toString(array: self.pointee.model)
+        }
+    }
+
+    var serial: String {
+        get {
+            toString(array: self.pointee.serial)
+        }
+    }
+
+    /// The human-readable description, e.g. "Dell Inc. DELL U2719D ABC123 (DP-1)"
+    var outputDescription: String {
+        get {
+            self.pointee.description.map { String(cString: $0) } ?? ""
+        }
+    }
 }
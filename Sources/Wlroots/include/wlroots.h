@@ -8,6 +8,7 @@
 #include <wlr/version.h>
 #include <wlr/backend.h>
 #include <wlr/backend/headless.h>
+#include <wlr/backend/libinput.h>
 #if WLR_VERSION_MINOR > 14
 #include <wlr/render/allocator.h>
 #endif
@@ -90,6 +90,13 @@ extension Awc {
         case .expand: self.modifyAndUpdate { $0.replace(layout: $0.current.workspace.layout.expand()) }
         case .close: self.kill()
         case .configReload: self.reloadConfig()
+        case .enterMode(let name):
+            if self.config.hasMode(name) {
+                self.mode = name
+            } else {
+                logger.warning("Cannot enter unknown mode \(name)")
+            }
+        case .exitMode: self.mode = nil
         case .focus(let nth): self.modifyAndUpdate { $0.modify { 
             // N.B. nth is 1-indexed
             $0.focus(nth: nth - 1)
@@ -108,7 +115,24 @@ extension Awc {
             self.modifyAndUpdate {
                 $0.replace(layout: nextLayout)
             }
-        case .resetLayouts: self.modifyAndUpdate { $0.replace(layout: self.defaultLayout) }
+        case .resetLayouts: self.modifyAndUpdate { viewSet in
+            viewSet.replace(
+                current: viewSet.current.copy(workspace: self.withConfiguredLayout(viewSet.current.workspace)),
+                visible: viewSet.visible.map { $0.copy(workspace: self.withConfiguredLayout($0.workspace)) },
+                hidden: viewSet.hidden.map { self.withConfiguredLayout($0) }
+            )
+        }
+        case .sequence(let actions):
+            let deferred = self.deferLayoutUpdates
+            self.deferLayoutUpdates = true
+            for action in actions {
+                self.execute(action: action)
+            }
+            self.deferLayoutUpdates = deferred
+            if !deferred {
+                self.updateLayout()
+                self.focusTop()
+            }
         case .moveTo(let tag): self.modifyAndUpdate { $0.shift(tag: tag) }
         case .moveToOutput(let n): self.withOutput(n) { self.execute(action: .moveTo(tag: $0.workspace.tag)) }
         case .shrink: self.modifyAndUpdate { $0.replace(layout: $0.current.workspace.layout.shrink()) }
@@ -160,25 +184,59 @@ extension Awc {
     }
 
     private func reloadConfig() {
-        if let config = loadConfig(path: self.config.path) {
-            // XXX This doesn't reload everything (e.g. border width)
+        let (maybeConfig, diagnostics) = loadConfig(
+            path: self.config.path,
+            forbidRemoteImports: self.config.forbidRemoteImports
+        )
+        if let config = maybeConfig {
+            let changes = config.changes(since: self.config)
             self.config = config
-            let layout = self.layoutWrapper(config.layout)
-            self.defaultLayout = layout
-            self.modifyAndUpdate { viewSet in
-                viewSet.replace(
-                    current: viewSet.current.copy(workspace: viewSet.current.workspace.replace(layout: layout)),
-                    visible: viewSet.visible.map { $0.copy(workspace: $0.workspace.replace(layout: layout)) },
-                    hidden: viewSet.hidden.map { $0.replace(layout: layout) }
-                )
+            if let mode = self.mode, !config.hasMode(mode) {
+                self.mode = nil
+            }
+            if changes.outputs {
+                self.configureOutputs()
+            }
+            if changes.layout {
+                self.defaultLayout = self.layoutWrapper(config.layout)
+            }
+            if changes.layout || !changes.changedWorkspaces.isEmpty {
+                let update: (Workspace<L>) -> Workspace<L> = {
+                    let ownLayout = config.workspace(tag: $0.tag)?.layout != nil
+                    if (changes.layout && !ownLayout) || changes.changedWorkspaces.contains($0.tag) {
+                        return self.withConfiguredLayout($0)
+                    }
+                    return $0
+                }
+                self.modifyAndUpdate { viewSet in
+                    viewSet.replace(
+                        current: viewSet.current.copy(workspace: update(viewSet.current.workspace)),
+                        visible: viewSet.visible.map { $0.copy(workspace: update($0.workspace)) },
+                        hidden: viewSet.hidden.map(update)
+                    )
+                }
             }
             logger.info("Reloaded config!")
-        } else {
-            do {
-                try executeCommand(self.config.generateErrorDisplayCmd(msg: "Reloading config failed :("))
-            } catch {
-                logger.warning("Could not display error message: \(error)")
+            let warnings = diagnostics + changes.unappliedChanges
+            if !warnings.isEmpty {
+                self.displayConfigDiagnostics(
+                    "Reloaded config with warnings:", warnings, using: config)
             }
+        } else {
+            self.displayConfigDiagnostics("Reloading config failed :(", diagnostics, using: self.config)
+        }
+    }
+
+    private func withConfiguredLayout(_ workspace: Workspace<L>) -> Workspace<L> {
+        workspace.replace(layout: self.layout(forWorkspace: workspace.tag))
+    }
+
+    func displayConfigDiagnostics(_ headline: String, _ diagnostics: [ConfigDiagnostic], using config: Config) {
+        let msg = ([headline] + diagnostics.map { $0.description }).joined(separator: "\n")
+        do {
+            try executeCommand(config.generateErrorDisplayCmd(msg: msg))
+        } catch {
+            logger.warning("Could not display error message: \(error)")
         }
     }
 }
@@ -191,6 +249,7 @@ extension Awc {
         case .move: self.setToFloatingAndMove(surface)
         case .resize: self.setToFloatingAndResize(surface)
         case .resizeByFrame: self.setToFloatingAndResizeByFrame(surface)
+        case .action(let action): self.execute(action: action)
         }
     }
 
@@ -6,6 +6,9 @@ struct AwcArguments: ParsableArguments {
         help: ArgumentHelp("Path to configuration file.", valueName: "path"))
     var configPath: String?
 
+    @Flag(help: "Fail to load the configuration if it imports anything from the network.")
+    var noRemoteImports: Bool = false
+
     @Flag()
     var debug: Bool = false
 }
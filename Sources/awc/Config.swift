@@ -20,7 +20,11 @@ public enum Action {
     /// Close focused surface
     case close
     case configReload
+    /// Replace the key bindings with the key bindings of the given mode
+    case enterMode(name: String)
     case execute(cmd: String)
+    /// Return to the regular key bindings
+    case exitMode
     /// Expand the main area
     case expand
     /// Move focus to next surface
@@ -48,6 +52,8 @@ public enum Action {
     case nextLayout
     /// Reset the layouts on the current workspace to default
     case resetLayouts
+    /// Execute the actions in order, the layout is only updated once afterwards
+    case sequence(actions: [Action])
     /// Shrink the main area
     case shrink
     /// Push focused surface back into tiling
@@ -68,6 +74,30 @@ public enum ButtonAction {
     case move
     case resize
     case resizeByFrame
+    /// Runs a regular action. The window under the cursor is focused first if the binding selects it
+    case action(Action)
+}
+
+/// What triggers a button binding: a pointer button (an evdev code) or scrolling.
+enum ButtonTrigger: Hashable {
+    case button(UInt32)
+    case scroll(ScrollDirection)
+}
+
+enum ScrollDirection {
+    case up
+    case down
+    case left
+    case right
+}
+
+enum Gesture {
+    case swipeUp
+    case swipeDown
+    case swipeLeft
+    case swipeRight
+    case pinchIn
+    case pinchOut
 }
 
 enum Key: Hashable {
@@ -75,19 +105,72 @@ enum Key: Hashable {
     case sym(sym: xkb_keysym_t)
 }
 
-enum KeyboardType {
-    case builtin
-    case external
-}
-
 enum WindowSelection {
     case focused
     case underCursor
 }
 
+enum WindowProperty {
+    case appId
+    case title
+    case windowClass
+}
+
+enum Pattern {
+    case exact(String)
+    case glob(String)
+    case regex(NSRegularExpression)
+
+    func matches(_ value: String) -> Bool {
+        switch self {
+        case .exact(let expected): return value == expected
+        case .glob(let pattern): return fnmatch(pattern, value, 0) == 0
+        case .regex(let regex):
+            let range = NSRange(value.startIndex..<value.endIndex, in: value)
+            return regex.firstMatch(in: value, range: range) != nil
+        }
+    }
+}
+
+struct WindowRule {
+    let matchers: [(WindowProperty, Pattern)]
+    let workspace: String?
+    /// One-indexed, 0 means no output
+    let output: UInt8
+    let float: Bool
+    let sink: Bool
+    let fullscreen: Bool
+
+    func matches(surface: Surface) -> Bool {
+        self.matchers.allSatisfy { (property, pattern) in
+            switch property {
+            case .appId: return pattern.matches(surface.appId)
+            case .title: return pattern.matches(surface.title)
+            case .windowClass: return pattern.matches(surface.windowClass)
+            }
+        }
+    }
+}
+
+/// The combined effects of all window rules matching a surface.
+struct WindowRuleEffects {
+    var workspace: String? = nil
+    var output: UInt8 = 0
+    /// `nil` if no rule decided whether the surface should float. `false` if a `Sink` rule
+    /// overrode an earlier `Float` rule.
+    var float: Bool? = nil
+    var fullscreen: Bool = false
+}
+
 public struct ButtonActionKey: Hashable {
     let modifiers: KeyModifiers
-    let button: UInt32
+    let trigger: ButtonTrigger
+}
+
+private struct GestureActionKey: Hashable {
+    let modifiers: KeyModifiers
+    let fingers: UInt32
+    let gesture: Gesture
 }
 
 private struct KeyActionKey: Hashable {
@@ -95,89 +178,289 @@ private struct KeyActionKey: Hashable {
     let key: Key
 }
 
+struct WorkspaceConfig {
+    let tag: String
+    /// The name to display, the tag if no name is configured
+    let name: String
+    /// `nil` if the workspace uses the config's layout
+    let layout: AnyLayout<Surface, OutputDetails>?
+    /// The output the workspace is initially shown on (1-indexed), 0 if any
+    let output: UInt8
+}
+
 class Config {
+    private let handle: OpaquePointer
     let path: String?
+    let forbidRemoteImports: Bool
     let borderWidth: UInt32
     let colors: AwcColorsConfig
+    let tabs: AwcTabsConfig
     let font: String
     let modifier: KeyModifiers
-    let outputConfigs: [String: (Int32, Int32, Float)]
     let layout: AnyLayout<Surface, OutputDetails>
-    let workspaces: [String]
+    let workspaces: [WorkspaceConfig]
+    private let windowRules: [WindowRule]
     private let displayErrorCmd: String
     private let buttonBindings: [ButtonActionKey: (ButtonAction, WindowSelection)]
+    private let gestureBindings: [GestureActionKey: Action]
     private let keyBindings: [KeyActionKey: Action]
-    private let keyboardConfigs: [(KeyboardType, String)]
+    private let modes: [String: [KeyActionKey: Action]]
 
     fileprivate init(
+        handle: OpaquePointer,
         path: String?,
+        forbidRemoteImports: Bool,
         borderWidth: UInt32,
         colors: AwcColorsConfig,
+        tabs: AwcTabsConfig,
         displayErrorCmd: String,
         font: String,
         modifier: KeyModifiers,
         buttonBindings: [ButtonActionKey: (ButtonAction, WindowSelection)],
+        gestureBindings: [GestureActionKey: Action],
         keyBindings: [KeyActionKey: Action],
-        keyboardConfigs: [(KeyboardType, String)],
-        outputConfigs: [String: (Int32, Int32, Float)],
+        modes: [String: [KeyActionKey: Action]],
         layout: AnyLayout<Surface, OutputDetails>,
-        workspaces: [String]
+        windowRules: [WindowRule],
+        workspaces: [WorkspaceConfig]
     ) {
+        self.handle = handle
         self.path = path
+        self.forbidRemoteImports = forbidRemoteImports
         self.borderWidth = borderWidth
         self.colors = colors
+        self.tabs = tabs
         self.displayErrorCmd = displayErrorCmd
         self.font = font
         self.modifier = modifier
         self.buttonBindings = buttonBindings
+        self.gestureBindings = gestureBindings
         self.keyBindings = keyBindings
-        self.keyboardConfigs = keyboardConfigs
-        self.outputConfigs = outputConfigs
+        self.modes = modes
         self.layout = layout
+        self.windowRules = windowRules
         self.workspaces = workspaces
     }
 
-    func configureKeyboard(vendor: UInt32) -> String {
-        for config in self.keyboardConfigs {
-            if config.0 == .builtin && vendor <= 1 {
-                return config.1
-            } else if config.0 == .external && vendor > 1 {
-                return config.1
-            }
+    deinit {
+        awc_config_handle_free(self.handle)
+    }
+
+    /// What changed in this config compared to `old`.
+    func changes(since old: Config) -> ConfigChanges {
+        var diff = AwcConfigDiff()
+        awc_config_diff(old.handle, self.handle, &diff)
+        defer {
+            awc_config_diff_free(&diff)
         }
-        return "de(nodeadkeys)"
+        return ConfigChanges(
+            layout: diff.layout_changed,
+            borderWidth: diff.border_width_changed,
+            colors: diff.colors_changed,
+            tabs: diff.tabs_changed,
+            keyboards: diff.keyboards_changed,
+            inputs: diff.inputs_changed,
+            outputs: diff.number_of_added_outputs + diff.number_of_removed_outputs
+                + diff.number_of_changed_outputs > 0 || diff.profiles_changed,
+            workspaces: diff.number_of_added_workspaces + diff.number_of_removed_workspaces
+                + diff.number_of_renamed_workspaces > 0,
+            changedWorkspaces: Set((0..<diff.number_of_changed_workspaces).map {
+                self.workspaces[diff.changed_workspaces[$0]].tag
+            })
+        )
+    }
+
+    /// Returns the merged settings of all input configs that match the device, `nil` if none matches.
+    func inputConfig(name: String, type: AwcInputType) -> AwcInputConfig? {
+        var result = AwcInputConfig()
+        return awc_config_handle_find_input(self.handle, name, type, &result) ? result : nil
+    }
+
+    /// Returns the first keyboard config that matches the keyboard, `nil` if none matches.
+    func keyboardConfig(name: String, vendor: UInt32) -> AwcKeyboardConfig? {
+        awc_config_handle_find_keyboard(self.handle, name, vendor <= 1 ? Builtin : External)?.pointee
     }
 
     func generateErrorDisplayCmd(msg: String) -> String {
-        // XXX shell escape
-        return "\(displayErrorCmd) \"\(msg)\""
+        let escaped = msg.replacingOccurrences(of: "'", with: "'\\''")
+        return "\(displayErrorCmd) '\(escaped)'"
+    }
+
+    func findButtonBinding(modifiers: KeyModifiers, trigger: ButtonTrigger) -> (ButtonAction, WindowSelection)? {
+        return self.buttonBindings[ButtonActionKey(modifiers: modifiers, trigger: trigger)]
     }
 
-    func findButtonBinding(modifiers: KeyModifiers, button: UInt32) -> (ButtonAction, WindowSelection)? {
-        return self.buttonBindings[ButtonActionKey(modifiers: modifiers, button: button)]
+    func findGestureBinding(modifiers: KeyModifiers, fingers: UInt32, gesture: Gesture) -> Action? {
+        return self.gestureBindings[GestureActionKey(modifiers: modifiers, fingers: fingers, gesture: gesture)]
     }
 
-    func findKeyBinding(modifiers: KeyModifiers, code: UInt32, sym: xkb_keysym_t) -> Action? {
-        if let action = self.keyBindings[KeyActionKey(modifiers: modifiers, key: Key.sym(sym: sym))] {
+    /// Finds the binding in the given mode's key bindings, or in the regular key bindings if
+    /// `mode` is `nil`.
+    func findKeyBinding(mode: String?, modifiers: KeyModifiers, code: UInt32, sym: xkb_keysym_t) -> Action? {
+        let keyBindings = mode.flatMap { self.modes[$0] } ?? self.keyBindings
+        if let action = keyBindings[KeyActionKey(modifiers: modifiers, key: Key.sym(sym: sym))] {
             return action
-        } else if let action = self.keyBindings[KeyActionKey(modifiers: modifiers, key: Key.code(code: code))] {
+        } else if let action = keyBindings[KeyActionKey(modifiers: modifiers, key: Key.code(code: code))] {
             return action
         }
         return nil
     }
+
+    /// Returns the config for each of the given outputs: the configs of the profile that matches the outputs if
+    /// there is one, otherwise the output config that matches each output best.
+    func outputConfigs(for outputs: [UnsafeMutablePointer<wlr_output>]) -> [AwcOutputConfig?] {
+        let strings = outputs.map {
+            [$0.name, $0.make, $0.model, $0.serial, $0.outputDescription].map { strdup($0)! }
+        }
+        defer {
+            strings.joined().forEach { free($0) }
+        }
+        let infos = strings.map {
+            AwcOutputInfo(name: $0[0], make: $0[1], model: $0[2], serial: $0[3], description: $0[4])
+        }
+
+        var configs = [UnsafePointer<AwcOutputConfig>?](repeating: nil, count: outputs.count)
+        if let profile = awc_config_handle_find_profile(self.handle, infos, infos.count, &configs) {
+            logger.info("Using output profile \(String(cString: profile.pointee.name))")
+            return configs.map { $0?.pointee }
+        }
+        return infos.map {
+            var info = $0
+            return awc_config_handle_find_output(self.handle, &info)?.pointee
+        }
+    }
+
+    func workspace(tag: String) -> WorkspaceConfig? {
+        self.workspaces.first(where: { $0.tag == tag })
+    }
+
+    func workspaceName(tag: String) -> String {
+        self.workspace(tag: tag)?.name ?? tag
+    }
+
+    func hasMode(_ name: String) -> Bool {
+        self.modes[name] != nil
+    }
+
+    /// Applies all matching window rules in order, later rules override earlier ones.
+    func findWindowRuleEffects(surface: Surface) -> WindowRuleEffects {
+        var effects = WindowRuleEffects()
+        for rule in self.windowRules where rule.matches(surface: surface) {
+            if let workspace = rule.workspace {
+                effects.workspace = workspace
+            }
+            if rule.output != 0 {
+                effects.output = rule.output
+            }
+            if rule.float {
+                effects.float = true
+            } else if rule.sink {
+                effects.float = false
+            }
+            effects.fullscreen = effects.fullscreen || rule.fullscreen
+        }
+        return effects
+    }
 }
 
-func loadConfig(path: String?) -> Config? {
-    var config = AwcConfig()
+/// A problem found while loading the config.
+struct ConfigDiagnostic: CustomStringConvertible {
+    let isError: Bool
+    let message: String
+    /// The setting the problem was found in, empty if it's not about a specific setting
+    let path: String
 
-    if let error = awc_config_load(path, &config) {
-        logger.critical("Could not load config: \(String(cString: error))")
-        awc_config_str_free(error)
-        return nil
+    var description: String {
+        get {
+            self.path.isEmpty ? self.message : "\(self.path): \(self.message)"
+        }
     }
-    defer {
-        awc_config_free(&config)
+}
+
+/// The settings that changed between two configs, as far as they matter for
+/// reloading.
+struct ConfigChanges {
+    let layout: Bool
+    let borderWidth: Bool
+    let colors: Bool
+    let tabs: Bool
+    let keyboards: Bool
+    let inputs: Bool
+    /// Whether outputs or profiles changed
+    let outputs: Bool
+    let workspaces: Bool
+    /// Tags of the workspaces whose name, layout or output changed
+    let changedWorkspaces: Set<String>
+
+    /// Changes that don't take effect by reloading the config.
+    var unappliedChanges: [ConfigDiagnostic] {
+        get {
+            var result: [ConfigDiagnostic] = []
+            if self.borderWidth {
+                result.append(ConfigDiagnostic(
+                    isError: false, message: "changes only take effect after a restart", path: "borderWidth"))
+            }
+            if self.colors {
+                result.append(ConfigDiagnostic(
+                    isError: false,
+                    message: "changes of border colors only take effect after a restart",
+                    path: "colors"))
+            }
+            if self.tabs {
+                result.append(ConfigDiagnostic(
+                    isError: false, message: "changes only take effect after a restart", path: "tabs"))
+            }
+            if self.keyboards {
+                result.append(ConfigDiagnostic(
+                    isError: false,
+                    message: "changes only take effect for newly connected keyboards",
+                    path: "keyboards"))
+            }
+            if self.inputs {
+                result.append(ConfigDiagnostic(
+                    isError: false,
+                    message: "changes only take effect for newly connected input devices",
+                    path: "inputs"))
+            }
+            if self.workspaces {
+                result.append(ConfigDiagnostic(
+                    isError: false, message: "changes only take effect after a restart", path: "workspaces"))
+            }
+            return result
+        }
     }
+}
+
+/// Loads the config. Returns `nil` if there was an error. All problems found
+/// in the config are returned as diagnostics, which are also logged.
+func loadConfig(path: String?, forbidRemoteImports: Bool = false) -> (Config?, [ConfigDiagnostic]) {
+    var options = AwcLoadOptions(forbid_remote_imports: forbidRemoteImports)
+    var awcDiagnostics = AwcDiagnostics()
+
+    let handle = awc_config_handle_load_with_options(path, &options, &awcDiagnostics)
+    var diagnostics: [ConfigDiagnostic] = []
+    for i in 0..<awcDiagnostics.number_of_diagnostics {
+        let diagnostic = awcDiagnostics.diagnostics[i]
+        diagnostics.append(ConfigDiagnostic(
+            isError: diagnostic.severity == AwcSeverity_Error,
+            message: String(cString: diagnostic.message),
+            path: String(cString: diagnostic.path)
+        ))
+    }
+    awc_config_diagnostics_free(&awcDiagnostics)
+
+    for diagnostic in diagnostics {
+        if diagnostic.isError {
+            logger.critical("Config error: \(diagnostic)")
+        } else {
+            logger.warning("Config warning: \(diagnostic)")
+        }
+    }
+
+    guard let handle = handle else {
+        return (nil, diagnostics)
+    }
+    let config = awc_config_handle_get(handle)!.pointee
 
     var buttonBindings: [ButtonActionKey: (ButtonAction, WindowSelection)] = [:]
     for i in 0..<config.number_of_button_bindings {
@@ -185,78 +468,122 @@ func loadConfig(path: String?) -> Config? {
             modifiers: toKeyModifiers(
               config.button_bindings[i].mods,
               config.button_bindings[i].number_of_mods),
-            button: toButton(config.button_bindings[i].button)
+            trigger: toButtonTrigger(config.button_bindings[i].button)
         )
-        buttonBindings[actionKey] = toButtonAction(config.button_bindings[i].action, config.button_bindings[i].window)
+        buttonBindings[actionKey] = toButtonAction(config.button_bindings[i])
     }
 
-    var keyBindings: [KeyActionKey: Action] = [:]
-    for i in 0..<config.number_of_key_bindings {
-        let key: Key
-        if let sym = config.key_bindings[i].sym {
-            let keySym = xkb_keysym_from_name(sym, XKB_KEYSYM_NO_FLAGS)
-            if keySym == 0 {
-                logger.warning("Unknown key symbol: \(String(cString: sym))")
-                continue
-            }
-            key = Key.sym(sym: keySym)
-        } else {
-            assert(config.key_bindings[i].code != 0)
-            key = Key.code(code: config.key_bindings[i].code)
-        }
-        let actionKey = KeyActionKey(
-            modifiers: toKeyModifiers(
-              config.key_bindings[i].mods,
-              config.key_bindings[i].number_of_mods),
-            key: key
+    var gestureBindings: [GestureActionKey: Action] = [:]
+    for i in 0..<config.number_of_gesture_bindings {
+        let binding = config.gesture_bindings[i]
+        let actionKey = GestureActionKey(
+            modifiers: toKeyModifiers(binding.mods, binding.number_of_mods),
+            fingers: UInt32(binding.fingers),
+            gesture: toGesture(binding.gesture)
         )
-        keyBindings[actionKey] = toAction(config.key_bindings[i].action)
+        gestureBindings[actionKey] = toAction(binding.action)
     }
 
-    var keyboardConfigs: [(KeyboardType, String)] = []
-    for i in 0..<config.number_of_keyboards {
-        let type: KeyboardType
-        if config.keyboards[i].type_ == Builtin {
-            type = .builtin
-        } else {
-            type = .external
+    let keyBindings = toKeyBindings(config.key_bindings, config.number_of_key_bindings)
+
+    var modes: [String: [KeyActionKey: Action]] = [:]
+    for i in 0..<config.number_of_modes {
+        let mode = config.modes[i]
+        modes[String(cString: mode.name)] = toKeyBindings(mode.key_bindings, mode.number_of_key_bindings)
+    }
+
+
+
+    var windowRules: [WindowRule] = []
+    for i in 0..<config.number_of_window_rules {
+        if let rule = toWindowRule(config.window_rules[i]) {
+            windowRules.append(rule)
         }
-        keyboardConfigs.append((type, String(cString: config.keyboards[i].layout)))
     }
 
-    var outputConfigs: [String: (Int32, Int32, Float)] = [:]
-    for i in 0..<config.number_of_outputs {
-        let scale: Float = config.outputs[i].scale
-        outputConfigs[String(cString: config.outputs[i].name)] =
-            (config.outputs[i].x, config.outputs[i].y, scale)
+    var layoutDiagnostics: [ConfigDiagnostic] = []
+    func buildLayoutOrReport(
+        _ ops: UnsafePointer<AwcLayoutOp>, _ numberOfOps: Int, path: String
+    ) -> AnyLayout<Surface, OutputDetails>? {
+        if let layout: AnyLayout<Surface, OutputDetails> = try? buildLayout(ops, numberOfOps) {
+            return layout
+        }
+        logger.error("Invalid layout at \(path)! Please use buildLayout")
+        layoutDiagnostics.append(ConfigDiagnostic(
+            isError: true,
+            message: "invalid layout, please use buildLayout",
+            path: path
+        ))
+        return nil
     }
 
-    var workspaces: [String] = []
+    var workspaces: [WorkspaceConfig] = []
     for i in 0..<config.number_of_workspaces {
-        workspaces.append(String(cString: config.workspaces[i]!))
+        let workspace = config.workspaces[i]
+        let tag = String(cString: workspace.tag)
+        workspaces.append(WorkspaceConfig(
+            tag: tag,
+            name: String(cString: workspace.name),
+            layout: workspace.layout.flatMap {
+                buildLayoutOrReport($0, workspace.number_of_layout_ops, path: "layout of workspace \(tag)")
+            },
+            output: workspace.output
+        ))
     }
 
-    guard let layout: AnyLayout<Surface, OutputDetails> = try?
-        buildLayout(config.layout, config.number_of_layout_ops)
-    else {
-        logger.error("Invalid layout! Please use buildLayout")
-        return nil
+    let maybeLayout = buildLayoutOrReport(config.layout, config.number_of_layout_ops, path: "layout")
+    guard let layout = maybeLayout, layoutDiagnostics.isEmpty else {
+        awc_config_handle_free(handle)
+        return (nil, diagnostics + layoutDiagnostics)
     }
 
-    return Config(
+    let loadedConfig = Config(
+        handle: handle,
         path: path,
+        forbidRemoteImports: forbidRemoteImports,
         borderWidth: config.border_width,
         colors: config.colors,
+        tabs: config.tabs,
         displayErrorCmd: String(cString: config.display_error_cmd),
         font: String(cString: config.font),
         modifier: toKeyModifiers(config.modifier),
         buttonBindings: buttonBindings,
+        gestureBindings: gestureBindings,
         keyBindings: keyBindings,
-        keyboardConfigs: keyboardConfigs,
-        outputConfigs: outputConfigs,
+        modes: modes,
         layout: layout,
+        windowRules: windowRules,
         workspaces: workspaces
     )
+    return (loadedConfig, diagnostics)
+}
+
+private func toKeyBindings(
+    _ bindings: UnsafePointer<AwcKeyBinding>?,
+    _ numberOfBindings: Int
+) -> [KeyActionKey: Action] {
+    var keyBindings: [KeyActionKey: Action] = [:]
+    for i in 0..<numberOfBindings {
+        let binding = bindings![i]
+        let key: Key
+        if let sym = binding.sym {
+            let keySym = xkb_keysym_from_name(sym, XKB_KEYSYM_NO_FLAGS)
+            if keySym == 0 {
+                logger.warning("Unknown key symbol: \(String(cString: sym))")
+                continue
+            }
+            key = Key.sym(sym: keySym)
+        } else {
+            assert(binding.code != 0)
+            key = Key.code(code: binding.code)
+        }
+        let actionKey = KeyActionKey(
+            modifiers: toKeyModifiers(binding.mods, binding.number_of_mods),
+            key: key
+        )
+        keyBindings[actionKey] = toAction(binding.action)
+    }
+    return keyBindings
 }
 
 private func toKeyModifiers(_ mods: UnsafePointer<AwcModifier>?, _ numberOfMods: Int) -> KeyModifiers {
@@ -301,6 +628,7 @@ private func assertExactlyOneAction(_ action: AwcAction) {
         , action.next_layout
         , action.assign_scratchpad
         , action.toggle_scratchpad
+        , action.exit_mode
         ].reduce(false, { assert(!$0 || !$1); return $0 || $1 })
 
     let numArgAction =
@@ -311,7 +639,8 @@ private func assertExactlyOneAction(_ action: AwcAction) {
         ].reduce(UInt8(0), { assert($0 == 0 || $1 == 0); return $0 + $1 })
 
     let stringArgAction: UnsafePointer<CChar>? =
-        [ action.execute
+        [ action.enter_mode
+        , action.execute
         , action.greedy_view
         , action.move_to
         , action.swap_workspace_tag_with
@@ -322,6 +651,7 @@ private func assertExactlyOneAction(_ action: AwcAction) {
         [ noArgAction
         , numArgAction != 0
         , stringArgAction != nil
+        , action.sequence != nil
         ].reduce(false, { assert(!$0 || !$1); return $0 || $1 })
     )
 }
@@ -332,6 +662,10 @@ private func toAction(_ action: AwcAction) -> Action {
         return .execute(cmd: String(cString: execute))
     } else if action.expand {
         return .expand
+    } else if let name = action.enter_mode {
+        return .enterMode(name: String(cString: name))
+    } else if action.exit_mode {
+        return .exitMode
     } else if let tag = action.move_to {
         return .moveTo(tag: String(cString: tag))
     } else if action.move_to_output != 0 {
@@ -376,32 +710,91 @@ private func toAction(_ action: AwcAction) -> Action {
         return .swapWorkspaceTagWith(tag: String(cString: tag))
     } else if action.next_layout {
         return .nextLayout
+    } else if let sequence = action.sequence {
+        return .sequence(actions: (0..<action.number_of_sequence_actions).map { toAction(sequence[$0]) })
     } else {
         return .switchVt(n: action.switch_vt)
     }
 }
 
-private func toButtonAction(_ action: AwcButtonAction, _ window: AwcWindowSelection) -> (ButtonAction, WindowSelection)
+private func toButtonAction(_ binding: AwcButtonBinding) -> (ButtonAction, WindowSelection)
 {
-    let selection: WindowSelection = window == Focused ? .focused : .underCursor
+    let selection: WindowSelection = binding.window == Focused ? .focused : .underCursor
+    let action = binding.action
     if action == Move {
         return (.move, selection)
     } else if action == Resize {
         return (.resize, selection)
     } else if action == ResizeByFrame {
         return (.resizeByFrame, selection)
+    } else if action == Run {
+        return (.action(toAction(binding.run.pointee)), selection)
     } else {
         fatalError("Unknown button action: \(action)")
     }
 }
 
-private func toButton(_ button: AwcButton) -> UInt32 {
-    if button ==  Left {
-        return UInt32(BTN_LEFT)
-    } else if button == Right {
-        return UInt32(BTN_RIGHT)
-    } else {
-        fatalError("Unknown button: \(button)")
+private func toWindowRule(_ rule: AwcWindowRule) -> WindowRule? {
+    var matchers: [(WindowProperty, Pattern)] = []
+    for i in 0..<rule.number_of_matchers {
+        let matcher = rule.matchers[i]
+        let property: WindowProperty
+        if matcher.property == AwcWindowProperty_AppId {
+            property = .appId
+        } else if matcher.property == AwcWindowProperty_Title {
+            property = .title
+        } else {
+            property = .windowClass
+        }
+
+        let pattern = String(cString: matcher.pattern)
+        if matcher.kind == AwcPatternKind_Exact {
+            matchers.append((property, .exact(pattern)))
+        } else if matcher.kind == AwcPatternKind_Glob {
+            matchers.append((property, .glob(pattern)))
+        } else {
+            guard let regex = try? NSRegularExpression(pattern: pattern) else {
+                logger.warning("Invalid regular expression in window rule: \(pattern)")
+                return nil
+            }
+            matchers.append((property, .regex(regex)))
+        }
+    }
+
+    return WindowRule(
+        matchers: matchers,
+        workspace: rule.workspace.map { String(cString: $0) },
+        output: rule.output,
+        float: rule.float,
+        sink: rule.sink,
+        fullscreen: rule.fullscreen
+    )
+}
+
+private func toButtonTrigger(_ button: AwcButton) -> ButtonTrigger {
+    switch button {
+    case Left: return .button(UInt32(BTN_LEFT))
+    case Right: return .button(UInt32(BTN_RIGHT))
+    case Middle: return .button(UInt32(BTN_MIDDLE))
+    case Side: return .button(UInt32(BTN_SIDE))
+    case Extra: return .button(UInt32(BTN_EXTRA))
+    case ScrollUp: return .scroll(.up)
+    case ScrollDown: return .scroll(.down)
+    case ScrollLeft: return .scroll(.left)
+    case ScrollRight: return .scroll(.right)
+    default: fatalError("Unknown button: \(button)")
+    }
+}
+
+private func toGesture(_ gesture: AwcGesture) -> Gesture {
+    switch gesture {
+    case AwcGesture_SwipeUp: return .swipeUp
+    case AwcGesture_SwipeDown: return .swipeDown
+    case AwcGesture_SwipeLeft: return .swipeLeft
+    case AwcGesture_SwipeRight: return .swipeRight
+    case AwcGesture_PinchIn: return .pinchIn
+    case AwcGesture_PinchOut: return .pinchOut
+    default: fatalError("Unknown gesture: \(gesture)")
     }
 }
 
@@ -278,6 +278,7 @@ func createRequestHandler<L: Layout>(awc: Awc<L>) -> (CtlClient, CtlRequest) thr
             let workspaces: [[String: Any]] = awc.viewSet.workspaces().map {
                 [
                     "tag": $0.tag,
+                    "name": awc.config.workspaceName(tag: $0.tag),
                     "views": $0.stack?.toList().map { $0.title } ?? []
                 ]
             }
@@ -320,6 +321,18 @@ func createRequestHandler<L: Layout>(awc: Awc<L>) -> (CtlClient, CtlRequest) thr
                 }
             }
             try client.write(response: "ok")
+        case .setLayoutByName(let name):
+            let layouts = sequence(
+                first: awc.viewSet.current.workspace.layout.firstLayout(),
+                next: { $0.nextLayout() })
+            if let newLayout = layouts.first(where: { $0.description == name }) {
+                awc.modifyAndUpdate {
+                    $0.replace(layout: newLayout)
+                }
+                try client.write(response: "ok")
+            } else {
+                try client.write(response: "error")
+            }
         }
     }
 }
@@ -39,6 +39,21 @@ func drawBorder(
     }
 }
 
+/// Fills the given box with a color.
+func drawRect(
+    renderer: UnsafeMutablePointer<wlr_renderer>,
+    output: UnsafeMutablePointer<wlr_output>,
+    box: wlr_box,
+    color: float_rgba
+) {
+    var mutableColor = color
+    mutableColor.withPtr { colorPtr in
+        withUnsafePointer(to: box) {
+            drawBox(renderer: renderer, output: output, box: $0, color: colorPtr)
+        }
+    }
+}
+
 private func drawBox(
     renderer: UnsafeMutablePointer<wlr_renderer>,
     output: UnsafeMutablePointer<wlr_output>,
@@ -7,6 +7,13 @@ enum Event {
     case cursorFrame(cursor: UnsafeMutablePointer<wlr_cursor>)
     case cursorMotion(event: UnsafeMutablePointer<wlr_event_pointer_motion>)
     case cursorMotionAbsolute(event: UnsafeMutablePointer<wlr_event_pointer_motion_absolute>)
+    /// Touchpad gestures, forwarded by the cursor.
+    case cursorSwipeBegin(event: UnsafeMutablePointer<wlr_event_pointer_swipe_begin>)
+    case cursorSwipeUpdate(event: UnsafeMutablePointer<wlr_event_pointer_swipe_update>)
+    case cursorSwipeEnd(event: UnsafeMutablePointer<wlr_event_pointer_swipe_end>)
+    case cursorPinchBegin(event: UnsafeMutablePointer<wlr_event_pointer_pinch_begin>)
+    case cursorPinchUpdate(event: UnsafeMutablePointer<wlr_event_pointer_pinch_update>)
+    case cursorPinchEnd(event: UnsafeMutablePointer<wlr_event_pointer_pinch_end>)
 
     case key(device: UnsafeMutablePointer<wlr_input_device>, event: UnsafeMutablePointer<wlr_event_keyboard_key>)
     case keyboardDestroyed(device: UnsafeMutablePointer<wlr_input_device>)
@@ -298,6 +298,12 @@ private struct CursorListener: Listener {
     private var frame: wl_listener = wl_listener()
     private var motion: wl_listener = wl_listener()
     private var motionAbsolute: wl_listener = wl_listener()
+    private var swipeBegin: wl_listener = wl_listener()
+    private var swipeUpdate: wl_listener = wl_listener()
+    private var swipeEnd: wl_listener = wl_listener()
+    private var pinchBegin: wl_listener = wl_listener()
+    private var pinchUpdate: wl_listener = wl_listener()
+    private var pinchEnd: wl_listener = wl_listener()
 
     mutating func listen(to cursor: UnsafeMutablePointer<wlr_cursor>) {
         Self.add(signal: &cursor.pointee.events.axis, listener: &self.axis) { (listener, data) in
@@ -333,6 +339,42 @@ private struct CursorListener: Listener {
                 { Event.cursorMotionAbsolute(event: $0) }
             )
         }
+
+        Self.add(signal: &cursor.pointee.events.swipe_begin, listener: &self.swipeBegin) { (listener, data) in
+            Self.emitEvent(
+                from: listener!, data: data!, \Self.swipeBegin, { Event.cursorSwipeBegin(event: $0) }
+            )
+        }
+
+        Self.add(signal: &cursor.pointee.events.swipe_update, listener: &self.swipeUpdate) { (listener, data) in
+            Self.emitEvent(
+                from: listener!, data: data!, \Self.swipeUpdate, { Event.cursorSwipeUpdate(event: $0) }
+            )
+        }
+
+        Self.add(signal: &cursor.pointee.events.swipe_end, listener: &self.swipeEnd) { (listener, data) in
+            Self.emitEvent(
+                from: listener!, data: data!, \Self.swipeEnd, { Event.cursorSwipeEnd(event: $0) }
+            )
+        }
+
+        Self.add(signal: &cursor.pointee.events.pinch_begin, listener: &self.pinchBegin) { (listener, data) in
+            Self.emitEvent(
+                from: listener!, data: data!, \Self.pinchBegin, { Event.cursorPinchBegin(event: $0) }
+            )
+        }
+
+        Self.add(signal: &cursor.pointee.events.pinch_update, listener: &self.pinchUpdate) { (listener, data) in
+            Self.emitEvent(
+                from: listener!, data: data!, \Self.pinchUpdate, { Event.cursorPinchUpdate(event: $0) }
+            )
+        }
+
+        Self.add(signal: &cursor.pointee.events.pinch_end, listener: &self.pinchEnd) { (listener, data) in
+            Self.emitEvent(
+                from: listener!, data: data!, \Self.pinchEnd, { Event.cursorPinchEnd(event: $0) }
+            )
+        }
     }
 
     mutating func deregister() {
@@ -341,6 +383,12 @@ private struct CursorListener: Listener {
         wl_list_remove(&self.frame.link)
         wl_list_remove(&self.motion.link)
         wl_list_remove(&self.motionAbsolute.link)
+        wl_list_remove(&self.swipeBegin.link)
+        wl_list_remove(&self.swipeUpdate.link)
+        wl_list_remove(&self.swipeEnd.link)
+        wl_list_remove(&self.pinchBegin.link)
+        wl_list_remove(&self.pinchUpdate.link)
+        wl_list_remove(&self.pinchEnd.link)
     }
 }
 
@@ -0,0 +1,84 @@
+//
+// Bindings for touchpad swipes and pinches
+//
+
+import Libawc
+import Wlroots
+
+/// How far the fingers have to move for a swipe, in the same units as pointer motion
+fileprivate let swipeDistance = 100.0
+/// How much the distance between the fingers has to change for a pinch, relative to the start
+fileprivate let pinchScale = 0.2
+
+fileprivate class GestureData {
+    var fingers: UInt32 = 0
+    var dx: Double = 0
+    var dy: Double = 0
+    var scale: Double = 1
+}
+
+extension Awc {
+    func beginGesture(fingers: UInt32) {
+        let data = self.getGestureData()
+        data.fingers = fingers
+        data.dx = 0
+        data.dy = 0
+        data.scale = 1
+    }
+
+    func updateSwipe(dx: Double, dy: Double) {
+        let data = self.getGestureData()
+        data.dx += dx
+        data.dy += dy
+    }
+
+    func updatePinch(scale: Double) {
+        // wlroots reports the scale relative to the start of the pinch
+        self.getGestureData().scale = scale
+    }
+
+    func endSwipe(cancelled: Bool) {
+        let data = self.getGestureData()
+        guard !cancelled && max(abs(data.dx), abs(data.dy)) >= swipeDistance else {
+            return
+        }
+        if abs(data.dx) > abs(data.dy) {
+            executeGestureBinding(data.dx < 0 ? .swipeLeft : .swipeRight, fingers: data.fingers)
+        } else {
+            executeGestureBinding(data.dy < 0 ? .swipeUp : .swipeDown, fingers: data.fingers)
+        }
+    }
+
+    func endPinch(cancelled: Bool) {
+        let data = self.getGestureData()
+        if cancelled {
+            return
+        } else if data.scale <= 1 - pinchScale {
+            executeGestureBinding(.pinchIn, fingers: data.fingers)
+        } else if data.scale >= 1 + pinchScale {
+            executeGestureBinding(.pinchOut, fingers: data.fingers)
+        }
+    }
+
+    private func executeGestureBinding(_ gesture: Gesture, fingers: UInt32) {
+        guard self.exclusiveClient == nil else {
+            return
+        }
+        let modifiers = self.seat.pointee.keyboard_state.keyboard.map {
+            KeyModifiers(rawValue: wlr_keyboard_get_modifiers($0))
+        } ?? KeyModifiers()
+        if let action = self.config.findGestureBinding(modifiers: modifiers, fingers: fingers, gesture: gesture) {
+            self.execute(action: action)
+        }
+    }
+
+    fileprivate func getGestureData() -> GestureData {
+        if let data: GestureData = self.getExtensionData() {
+            return data
+        } else {
+            let data = GestureData()
+            self.addExtensionData(data)
+            return data
+        }
+    }
+}
@@ -0,0 +1,102 @@
+//
+// libinput configuration of pointers and touchpads
+//
+
+import Logging
+
+import awc_config
+import Libawc
+import Wlroots
+
+fileprivate let logger = Logger(label: "Input Config")
+
+extension Awc {
+    /// Applies the matching input configs to the device, if it's a libinput device.
+    func configureInput(_ device: UnsafeMutablePointer<wlr_input_device>) {
+        guard wlr_input_device_is_libinput(device) else {
+            return
+        }
+        let handle = wlr_libinput_get_device_handle(device)!
+        // Like sway, consider everything that supports tapping a touchpad
+        let type = libinput_device_config_tap_get_finger_count(handle) > 0
+            ? AwcInputType_Touchpad
+            : AwcInputType_Pointer
+        let name = device.pointee.name.map { String(cString: $0) } ?? ""
+        guard let inputConfig = self.config.inputConfig(name: name, type: type) else {
+            return
+        }
+        logger.debug("Configuring input device \(name)")
+
+        check(name, "tap") {
+            toggle(inputConfig.tap).map {
+                libinput_device_config_tap_set_enabled(
+                    handle, $0 ? LIBINPUT_CONFIG_TAP_ENABLED : LIBINPUT_CONFIG_TAP_DISABLED)
+            }
+        }
+        check(name, "naturalScroll") {
+            toggle(inputConfig.natural_scroll).map {
+                libinput_device_config_scroll_set_natural_scroll_enabled(handle, $0 ? 1 : 0)
+            }
+        }
+        check(name, "accelProfile") {
+            accelProfile(inputConfig.accel_profile).map {
+                libinput_device_config_accel_set_profile(handle, $0)
+            }
+        }
+        check(name, "accelSpeed") {
+            inputConfig.has_accel_speed
+                ? libinput_device_config_accel_set_speed(handle, inputConfig.accel_speed)
+                : nil
+        }
+        check(name, "scrollMethod") {
+            scrollMethod(inputConfig.scroll_method).map {
+                libinput_device_config_scroll_set_method(handle, $0)
+            }
+        }
+        check(name, "disableWhileTyping") {
+            toggle(inputConfig.disable_while_typing).map {
+                libinput_device_config_dwt_set_enabled(
+                    handle, $0 ? LIBINPUT_CONFIG_DWT_ENABLED : LIBINPUT_CONFIG_DWT_DISABLED)
+            }
+        }
+        check(name, "leftHanded") {
+            toggle(inputConfig.left_handed).map {
+                libinput_device_config_left_handed_set(handle, $0 ? 1 : 0)
+            }
+        }
+    }
+}
+
+/// Logs a warning if the setting couldn't be applied. `apply` returns `nil` if the setting isn't configured.
+private func check(_ device: String, _ setting: String, _ apply: () -> libinput_config_status?) {
+    if let status = apply(), status != LIBINPUT_CONFIG_STATUS_SUCCESS {
+        let reason = String(cString: libinput_config_status_to_str(status))
+        logger.warning("Could not set \(setting) of input device \(device): \(reason)")
+    }
+}
+
+private func toggle(_ toggle: AwcToggle) -> Bool? {
+    switch toggle {
+    case AwcToggle_Enabled: return true
+    case AwcToggle_Disabled: return false
+    default: return nil
+    }
+}
+
+private func accelProfile(_ profile: AwcAccelProfile) -> libinput_config_accel_profile? {
+    switch profile {
+    case AwcAccelProfile_Flat: return LIBINPUT_CONFIG_ACCEL_PROFILE_FLAT
+    case AwcAccelProfile_Adaptive: return LIBINPUT_CONFIG_ACCEL_PROFILE_ADAPTIVE
+    default: return nil
+    }
+}
+
+private func scrollMethod(_ method: AwcScrollMethod) -> libinput_config_scroll_method? {
+    switch method {
+    case AwcScrollMethod_NoScroll: return LIBINPUT_CONFIG_SCROLL_NO_SCROLL
+    case AwcScrollMethod_TwoFinger: return LIBINPUT_CONFIG_SCROLL_2FG
+    case AwcScrollMethod_Edge: return LIBINPUT_CONFIG_SCROLL_EDGE
+    case AwcScrollMethod_OnButtonDown: return LIBINPUT_CONFIG_SCROLL_ON_BUTTON_DOWN
+    default: return nil
+    }
+}
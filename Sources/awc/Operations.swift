@@ -67,24 +67,52 @@ extension Awc {
     /// Modifies the view set with given function and then updates.
     func modifyAndUpdate(_ f: (ViewSet<L, Surface>) -> ViewSet<L, Surface>) {
         self.viewSet = f(self.viewSet)
-        self.updateLayout()
-        self.focusTop()
+        if !self.deferLayoutUpdates {
+            self.updateLayout()
+            self.focusTop()
+        }
     }
 
-    /// Adds a new surface to be managed in the current workspace and brings it into focus.
+    /// Adds a new surface to be managed and brings it into focus. The surface is added to the
+    /// current workspace, unless a window rule moves it elsewhere.
     func manage(surface: Surface) {
-        let wantsFloating = surface.wantsFloating(awc: self) || self.shouldFloat(surface: surface)
-        if !wantsFloating {
+        let effects = self.config.findWindowRuleEffects(surface: surface)
+        // Sink only overrides floating by an earlier rule, dialogs and popups always float
+        let wantsFloating = effects.fullscreen || effects.float == true || surface.wantsFloating(awc: self)
+        if effects.fullscreen {
+            surface.setFullscreen(true)
+        } else if !wantsFloating {
             surface.setTiled()
         }
-        self.modifyAndUpdate {
+
+        var targetTag = effects.workspace
+        if targetTag == nil && effects.output != 0 {
+            let outputs = self.orderedOutputs()
+            if effects.output <= outputs.count {
+                targetTag = outputs[Int(effects.output - 1)].workspace.tag
+            }
+        }
+
+        let insert: (ViewSet<L, Surface>) -> ViewSet<L, Surface> = {
             var viewSet = $0.modifyOr(default: Stack.singleton(surface), { $0.insert(surface) })
-            if wantsFloating {
-                let floatingBox = surface.preferredFloatingBox(awc: self, output: self.viewSet.current)
+            if effects.fullscreen {
+                let outputBox = viewSet.current.data.box
+                viewSet = viewSet.float(
+                    view: surface,
+                    box: wlr_box(x: 0, y: 0, width: outputBox.width, height: outputBox.height))
+            } else if wantsFloating {
+                let floatingBox = surface.preferredFloatingBox(awc: self, output: viewSet.current)
                 viewSet = viewSet.float(view: surface, box: floatingBox)
             }
             return viewSet
         }
+        self.modifyAndUpdate { viewSet in
+            if let tag = targetTag, viewSet.workspaces().contains(where: { $0.tag == tag }) {
+                return viewSet.onWorkspace(tag: tag, insert)
+            } else {
+                return insert(viewSet)
+            }
+        }
     }
 
     func updateLayout() {
@@ -121,6 +149,7 @@ extension Awc {
         if self.outputHudVisible {
             self.viewSet.current.data.hud?.update(
                 output: self.viewSet.current,
+                workspaceName: self.config.workspaceName(tag: self.viewSet.current.workspace.tag),
                 renderer: self.renderer,
                 font: self.config.font,
                 colors: self.config.colors.output_hud
@@ -150,4 +179,9 @@ extension Awc {
     func orderedOutputs() -> [Output<L>] {
         self.viewSet.outputs().sorted(by: { $0.data.box.x <= $1.data.box.x })
     }
+
+    /// The layout configured for the workspace with the given tag, the default layout if it has none
+    func layout(forWorkspace tag: String) -> L {
+        self.config.workspace(tag: tag)?.layout.map(self.layoutWrapper) ?? self.defaultLayout
+    }
 }
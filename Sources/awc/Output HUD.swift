@@ -47,6 +47,7 @@ public class OutputHud {
 
     public func update<L: Layout>(
         output: Output<L>,
+        workspaceName: String,
         renderer: UnsafeMutablePointer<wlr_renderer>,
         font: String,
         colors: AwcOutputHudColors
@@ -62,7 +63,7 @@ public class OutputHud {
 
         let name = output.data.output.name
         let (outputAndTagPositionX, outputAndTagSurface) = renderTagAndOutputName(
-            tag: output.workspace.tag, outputName: name, font: font, colors: colors)
+            tag: workspaceName, outputName: name, font: font, colors: colors)
 
         let rects: [(wlr_box, float_rgba)]
         let titleSurfaces: [(Int32, Int32, Cairo.Surface)]
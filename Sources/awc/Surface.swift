@@ -79,6 +79,14 @@ extension Surface {
         }
     }
 
+    func setFullscreen(_ fullscreen: Bool) {
+        switch self {
+        case .layer: ()
+        case .xdg(let surface): wlr_xdg_toplevel_set_fullscreen(surface, fullscreen)
+        case .xwayland(let surface): wlr_xwayland_surface_set_fullscreen(surface, fullscreen)
+        }
+    }
+
     func popupOf(wlrXWaylandSurface: UnsafeMutablePointer<wlr_xwayland_surface>) -> Bool {
         switch self {
         case .layer, .xdg: return false
@@ -106,6 +114,25 @@ extension Surface {
         }
     }
 
+    var appId: String {
+        get {
+            switch self {
+            case .layer, .xwayland: return ""
+            case .xdg(let surface): return surface.pointee.toplevel.pointee.app_id.toString()
+            }
+        }
+    }
+
+    /// The window class of XWayland surfaces
+    var windowClass: String {
+        get {
+            switch self {
+            case .layer, .xdg: return ""
+            case .xwayland(let surface): return surface.pointee.`class`.toString()
+            }
+        }
+    }
+
     var wlrSurface: UnsafeMutablePointer<wlr_surface> {
         get {
             switch self {
@@ -0,0 +1,209 @@
+//
+// Tab strips for views that are marked as `tabbed` by the layout (see `Tabbed`)
+//
+
+import awc_config
+import Cairo
+import CCairo
+import Drm
+import Libawc
+import Wlroots
+
+/// Moves views that are marked as `tabbed` down to make room for the tab strip above them.
+final class TabStripLayout<Wrapped: Layout>: Layout {
+    public typealias View = Wrapped.View
+    public typealias OutputData = Wrapped.OutputData
+
+    public var description: String {
+        get {
+            self.layout.description
+        }
+    }
+
+    private let height: Int32
+    private let layout: Wrapped
+
+    init(height: Int32, layout: Wrapped) {
+        self.height = height
+        self.layout = layout
+    }
+
+    public func emptyLayout<L: Layout>(
+        dataProvider: ExtensionDataProvider,
+        output: Output<L>,
+        box: wlr_box
+    ) -> [(L.View, Set<ViewAttribute>, wlr_box)] where Wrapped.View == L.View, Wrapped.OutputData == L.OutputData {
+        self.layout.emptyLayout(dataProvider: dataProvider, output: output, box: box)
+    }
+
+    public func doLayout<L: Layout>(
+        dataProvider: ExtensionDataProvider,
+        output: Output<L>,
+        stack: Libawc.Stack<L.View>,
+        box: wlr_box
+    ) -> [(L.View, Set<ViewAttribute>, wlr_box)] where Wrapped.View == L.View, Wrapped.OutputData == L.OutputData {
+        self.layout.doLayout(dataProvider: dataProvider, output: output, stack: stack, box: box).map {
+            guard $0.1.contains(.tabbed) else {
+                return $0
+            }
+            let height = min(self.height, $0.2.height - 1)
+            return ($0.0, $0.1, wlr_box(x: $0.2.x, y: $0.2.y + height, width: $0.2.width, height: $0.2.height - height))
+        }
+    }
+
+    func firstLayout() -> TabStripLayout<Wrapped> {
+        TabStripLayout(height: self.height, layout: self.layout.firstLayout())
+    }
+
+    func nextLayout() -> TabStripLayout<Wrapped>? {
+        self.layout.nextLayout().map { TabStripLayout(height: self.height, layout: $0) }
+    }
+
+    public func expand() -> TabStripLayout<Wrapped> {
+        TabStripLayout(height: self.height, layout: self.layout.expand())
+    }
+
+    public func shrink() -> TabStripLayout<Wrapped> {
+        TabStripLayout(height: self.height, layout: self.layout.shrink())
+    }
+}
+
+// Exists solely because tuples aren't hashable in Swift
+private struct TitleCacheKey: Equatable, Hashable {
+    let title: String
+    let width: Int32
+    let height: Int32
+    let active: Bool
+}
+
+/// Renders the tab strip above a tabbed view: one tab per tiled view of the workspace, showing
+/// its title.
+private final class TabStripRenderer {
+    private let height: Int32
+    private let font: String
+    private let colors: AwcTabsConfig
+    private var titleSurfaceCache: LRUCache<TitleCacheKey, Cairo.Surface> = LRUCache(maxSize: 64)
+
+    init(height: Int32, font: String, colors: AwcTabsConfig) {
+        self.height = height
+        self.font = font
+        self.colors = colors
+    }
+
+    func render<L: Layout>(awc: Awc<L>, output: Output<L>, box viewBox: wlr_box)
+    where L.View == Surface, L.OutputData == OutputDetails {
+        guard let stack = output.workspace.stack?.filter({ !awc.viewSet.floating.contains(key: $0) }) else {
+            return
+        }
+        let height = min(self.height, viewBox.y)
+        guard height > 0 else {
+            return
+        }
+        let views = stack.toArray()
+        let wlrOutput = output.data.output
+        let scale = Double(wlrOutput.pointee.scale)
+
+        var remainingWidth = viewBox.width
+        var currentX = viewBox.x
+        for (i, view) in views.enumerated() {
+            let width = remainingWidth / Int32(views.count - i)
+            let tab = wlr_box(x: currentX, y: viewBox.y - height, width: width, height: height)
+            currentX += width
+            remainingWidth -= width
+
+            let active = view == stack.focus
+            drawRect(
+                renderer: awc.renderer,
+                output: wlrOutput,
+                box: tab,
+                color: (active ? self.colors.active_background : self.colors.inactive_background).toFloatRgba())
+
+            let title = self.renderTitle(
+                view.title,
+                width: Int32(Double(tab.width) * scale),
+                height: Int32(Double(tab.height) * scale),
+                active: active)
+            self.render(surface: title, renderer: awc.renderer, output: wlrOutput, at: tab.scale(scale))
+        }
+    }
+
+    private func renderTitle(_ title: String, width: Int32, height: Int32, active: Bool) -> Cairo.Surface {
+        let key = TitleCacheKey(title: title, width: width, height: height, active: active)
+        if let surface = self.titleSurfaceCache.get(forKey: key) {
+            return surface
+        }
+
+        let padding = Double(height) / 4
+        let fontSize = Double(height) * 0.6
+        let surface = Cairo.Surface(width: max(1, width), height: max(1, height))
+        let cairo = surface.context
+        cairo.selectFontFace(family: self.font)
+        cairo.set(fontSize: fontSize)
+        var textExtents = cairo_text_extents_t()
+        cairo.extents(text: title, &textExtents)
+
+        // Clip, so that long titles don't run into the next tab
+        cairo.rectangle(x: padding, y: 0, width: max(0, Double(width) - 2 * padding), height: Double(height))
+        cairo.clip()
+        let color = active ? self.colors.active_foreground : self.colors.inactive_foreground
+        cairo.setSource(
+            r: Double(color.r) / 255.0,
+            g: Double(color.g) / 255.0,
+            b: Double(color.b) / 255.0,
+            a: Double(color.a) / 255.0)
+        cairo.moveTo(
+            x: padding - textExtents.x_bearing,
+            y: Double(height) / 2 - (textExtents.height / 2 + textExtents.y_bearing))
+        cairo.show(text: title)
+
+        self.titleSurfaceCache[key] = surface
+        return surface
+    }
+
+    private func render(
+        surface: Cairo.Surface,
+        renderer: UnsafeMutablePointer<wlr_renderer>,
+        output: UnsafeMutablePointer<wlr_output>,
+        at box: wlr_box
+    ) {
+        let texture: UnsafeMutablePointer<wlr_texture>? = surface.withRawPointer {
+            cairo_surface_flush($0)
+            return wlr_texture_from_pixels(
+                renderer,
+                _DRM_FORMAT_ARGB8888,
+                UInt32(cairo_image_surface_get_stride($0)),
+                UInt32(surface.width),
+                UInt32(surface.height),
+                cairo_image_surface_get_data($0))
+        }
+        guard let texture = texture else {
+            return
+        }
+        defer {
+            wlr_texture_destroy(texture)
+        }
+
+        withUnsafePointer(to: &output.pointee.transform_matrix.0) { outputTransformMatrixPtr in
+            _ = wlr_render_texture(renderer, texture, outputTransformMatrixPtr, box.x, box.y, 1)
+        }
+    }
+}
+
+/// Draws the tab strip for views that are marked as `tabbed`.
+public func tabStrips<L: Layout>(
+    height: UInt32,
+    font: String,
+    colors: AwcTabsConfig,
+    _ renderHook: @escaping RenderSurfaceHook<L>
+) -> RenderSurfaceHook<L>
+    where L.View == Surface, L.OutputData == OutputDetails
+{
+    let renderer = TabStripRenderer(height: Int32(clamping: height), font: font, colors: colors)
+    return { awc, output, surface, attributes, box in
+        if attributes.contains(.tabbed) {
+            renderer.render(awc: awc, output: output, box: box)
+        }
+
+        renderHook(awc, output, surface, attributes, box)
+    }
+}
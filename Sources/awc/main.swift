@@ -127,6 +127,8 @@ public class Awc<L: Layout> where L.View == Surface, L.OutputData == OutputDetai
     var draggingStart: (UnsafeMutablePointer<wlr_surface>, (Double, Double))? = nil
     /// How many pointer buttons are currently pressed
     internal var buttonsPressed: Int = 0
+    /// Scroll distance that didn't trigger a scroll binding yet, for scrolling without discrete steps
+    private var pendingScroll: (direction: ScrollDirection, distance: Double)? = nil
     // Additional overlay surfaces, for example Drag and Drop icons
     internal var surfaces: [UnsafeMutablePointer<wlr_surface>: (Double, Double)] = [:]
     // Hook to render something on top of surfaces and layers, such as the output HUD or a resizing frame
@@ -136,6 +138,12 @@ public class Awc<L: Layout> where L.View == Surface, L.OutputData == OutputDetai
     // Whether the "output HUD" is visible
     internal var outputHudVisible: Bool = false
     internal var config: Config
+    // The active binding mode, `nil` if the regular key bindings are active
+    internal var mode: String? = nil
+    // All connected outputs, including disabled ones
+    private var connectedOutputs: [UnsafeMutablePointer<wlr_output>] = []
+    // Whether modifyAndUpdate only modifies, e.g. while executing a sequence of actions
+    internal var deferLayoutUpdates: Bool = false
     /// Whether the default cursor image is shown
     private var defaultCursorImageShown: Bool = false
 
@@ -157,9 +165,15 @@ public class Awc<L: Layout> where L.View == Surface, L.OutputData == OutputDetai
         viewAtHook: @escaping ViewAtHook<L>,
         config: Config
     ) {
+        func workspaceLayout(_ workspace: WorkspaceConfig) -> L {
+            workspace.layout.map(layoutWrapper) ?? layout
+        }
+
+        // Start on the workspace configured for the first output
+        let initial = config.workspaces.firstIndex(where: { $0.output == 1 }) ?? 0
         let workspace: Workspace<L> = Workspace(
-            tag: config.workspaces.first ?? "1",
-            layout: layout
+            tag: config.workspaces.isEmpty ? "1" : config.workspaces[initial].tag,
+            layout: config.workspaces.isEmpty ? layout : workspaceLayout(config.workspaces[initial])
         )
         self.noOpOutputDamage = wlr_output_damage_create(noOpOutput)
         let output = Output(
@@ -167,10 +181,8 @@ public class Awc<L: Layout> where L.View == Surface, L.OutputData == OutputDetai
             workspace: workspace
         )
         var otherWorkspaces: [Workspace<L>] = []
-        if config.workspaces.count > 1 {
-            for tag in config.workspaces[1...] {
-                otherWorkspaces.append(Workspace(tag: tag, layout: layout))
-            }
+        for (i, workspaceConfig) in config.workspaces.enumerated() where i != initial {
+            otherWorkspaces.append(Workspace(tag: workspaceConfig.tag, layout: workspaceLayout(workspaceConfig)))
         }
         self.viewSet = ViewSet(current: output, hidden: otherWorkspaces)
         self.wlDisplay = wlDisplay
@@ -246,19 +258,6 @@ public class Awc<L: Layout> where L.View == Surface, L.OutputData == OutputDetai
         }
         return nil
     }
-
-    func shouldFloat(surface: Surface) -> Bool {
-        // XXX This should come from some configuration file
-        if case .xdg(let xdgSurface) = surface {
-            if xdgSurface.pointee.role == WLR_XDG_SURFACE_ROLE_TOPLEVEL &&
-                       xdgSurface.pointee.toplevel.pointee.app_id != nil
-            {
-                let appId = String(cString: xdgSurface.pointee.toplevel.pointee.app_id)
-                return appId == "whisker-menu"
-            }
-        }
-        return false
-    }
 }
 
 extension Awc: ExtensionDataProvider {
@@ -277,6 +276,12 @@ extension Awc {
         case .cursorFrame: handleCursorFrame()
         case .cursorMotion(let cursor): handleCursorMotion(cursor)
         case .cursorMotionAbsolute(let cursor): handleCursorMotionAbsolute(cursor)
+        case .cursorSwipeBegin(let event): beginGesture(fingers: event.pointee.fingers)
+        case .cursorSwipeUpdate(let event): updateSwipe(dx: event.pointee.dx, dy: event.pointee.dy)
+        case .cursorSwipeEnd(let event): endSwipe(cancelled: event.pointee.cancelled)
+        case .cursorPinchBegin(let event): beginGesture(fingers: event.pointee.fingers)
+        case .cursorPinchUpdate(let event): updatePinch(scale: event.pointee.scale)
+        case .cursorPinchEnd(let event): endPinch(cancelled: event.pointee.cancelled)
         case .key(let device, let keyEvent): handleKey(device, keyEvent)
         case .keyboardDestroyed(let device): handleKeyboardDestroyed(device)
         case .modifiers(let device): handleModifiers(device)
@@ -287,6 +292,28 @@ extension Awc {
     }
 
     private func handleCursorAxis(_ event: UnsafeMutablePointer<wlr_event_pointer_axis>) {
+        if self.exclusiveClient == nil,
+           let keyboard = self.seat.pointee.keyboard_state.keyboard,
+           event.pointee.delta != 0
+        {
+            let direction: ScrollDirection
+            if event.pointee.orientation == WLR_AXIS_ORIENTATION_VERTICAL {
+                direction = event.pointee.delta < 0 ? .up : .down
+            } else {
+                direction = event.pointee.delta < 0 ? .left : .right
+            }
+            let modifiers = KeyModifiers(rawValue: wlr_keyboard_get_modifiers(keyboard))
+            if let (action, windowSelection) = self.config.findButtonBinding(
+                modifiers: modifiers, trigger: .scroll(direction)
+            ) {
+                for _ in 0..<scrollSteps(direction, event.pointee) {
+                    execute(action: action, windowSelection: windowSelection)
+                }
+                return
+            }
+        }
+        self.pendingScroll = nil
+
         wlr_seat_pointer_notify_axis(
             self.seat,
             event.pointee.time_msec,
@@ -297,6 +324,47 @@ extension Awc {
         )
     }
 
+    /// How many steps to scroll. Wheels report discrete steps, for everything else (e.g. touchpads) the
+    /// distance is accumulated until it's as far as one step of a typical wheel.
+    private func scrollSteps(_ direction: ScrollDirection, _ event: wlr_event_pointer_axis) -> Int {
+        if event.delta_discrete != 0 {
+            self.pendingScroll = nil
+            return Int(abs(event.delta_discrete))
+        }
+        let stepDistance = 15.0
+        var distance = abs(event.delta)
+        if let pending = self.pendingScroll, pending.direction == direction {
+            distance += pending.distance
+        }
+        let steps = Int(distance / stepDistance)
+        self.pendingScroll = (direction, distance - Double(steps) * stepDistance)
+        return steps
+    }
+
+    /// Executes a button binding's action on the window selected by the binding. Regular actions that
+    /// apply to the focused window don't need a window and run in any case.
+    private func execute(action: ButtonAction, windowSelection: WindowSelection) {
+        if case .action(let action) = action, windowSelection == .focused {
+            self.execute(action: action)
+        } else if windowSelection == .focused {
+            self.withFocused {
+                execute(action: action, surface: $0)
+            }
+        } else if let (parent, _, _, _) = self.viewAtHook(self, self.cursor.pointee.x, self.cursor.pointee.y) {
+            switch parent {
+            case .layer: ()
+            default:
+                self.modifyAndUpdate {
+                    $0.focus(view: parent)
+                }
+            }
+            execute(action: action, surface: parent)
+        } else if case .action(let action) = action {
+            // Actions such as switching workspaces don't need a window
+            self.execute(action: action)
+        }
+    }
+
     private func handleCursorButton(_ event: UnsafeMutablePointer<wlr_event_pointer_button>) {
         wlr_idle_notify_activity(self.idle, self.seat)
 
@@ -312,11 +380,10 @@ extension Awc {
             let maybeAction: (ButtonAction, WindowSelection)?
             if let keyboard = self.seat.pointee.keyboard_state.keyboard {
                 let modifiers = KeyModifiers(rawValue: wlr_keyboard_get_modifiers(keyboard))
-                maybeAction = self.config.findButtonBinding(modifiers: modifiers, button: event.pointee.button)
+                maybeAction = self.config.findButtonBinding(
+                    modifiers: modifiers, trigger: .button(event.pointee.button))
                 if let (action, windowSelection) = maybeAction, windowSelection == .focused {
-                    self.withFocused {
-                        execute(action: action, surface: $0)
-                    }
+                    execute(action: action, windowSelection: windowSelection)
                     return
                 }
             } else {
@@ -348,6 +415,10 @@ extension Awc {
                     execute(action: action, surface: parent)
                     return
                 }
+            } else if case .action(let action) = maybeAction?.0 {
+                // Actions such as switching workspaces don't need a window
+                self.execute(action: action)
+                return
             }
         }
 
@@ -426,18 +497,47 @@ extension Awc {
     private func handleNewInput(_ device: UnsafeMutablePointer<wlr_input_device>) {
         if device.pointee.type == WLR_INPUT_DEVICE_KEYBOARD {
             let context = xkb_context_new(XKB_CONTEXT_NO_FLAGS)
-            let layout = self.config.configureKeyboard(vendor: device.pointee.vendor)
-            let keymap: OpaquePointer = layout.withCString { layoutPtr in
-                "compose:rctrl".withCString { optionsPtr in
-                    var rules = xkb_rule_names()
-                    rules.layout = layoutPtr
-                    rules.options = optionsPtr
+            let name = device.pointee.name.map { String(cString: $0) } ?? ""
+            let keyboardConfig = self.config.keyboardConfig(name: name, vendor: device.pointee.vendor)
+            var rules = xkb_rule_names()
+            if let keyboardConfig = keyboardConfig {
+                rules.layout = keyboardConfig.layout
+                rules.variant = keyboardConfig.variant
+                rules.model = keyboardConfig.model
+                rules.options = keyboardConfig.options
+            }
+            // Without a matching keyboard config, use the default layout and the default options of
+            // `Keyboard` in Types.dhall
+            let keymap = "de(nodeadkeys)".withCString { defaultLayout -> OpaquePointer in
+                "compose:rctrl".withCString { defaultOptions in
+                    if keyboardConfig == nil {
+                        rules.layout = defaultLayout
+                        rules.options = defaultOptions
+                    }
                     return xkb_keymap_new_from_names(context, &rules, XKB_KEYMAP_COMPILE_NO_FLAGS)
                 }
             }
             wlr_keyboard_set_keymap(device.pointee.keyboard, keymap)
 
-            wlr_keyboard_set_repeat_info(device.pointee.keyboard, 25, 600)
+            wlr_keyboard_set_repeat_info(
+                device.pointee.keyboard,
+                keyboardConfig?.repeat_rate ?? 25,
+                keyboardConfig?.repeat_delay ?? 600
+            )
+
+            if keyboardConfig?.numlock == true {
+                let numlock = xkb_keymap_mod_get_index(keymap, XKB_MOD_NAME_NUM)
+                if numlock != xkb_mod_index_t(XKB_MOD_INVALID) {
+                    let modifiers = device.pointee.keyboard.pointee.modifiers
+                    wlr_keyboard_notify_modifiers(
+                        device.pointee.keyboard,
+                        modifiers.depressed,
+                        modifiers.latched,
+                        modifiers.locked | (1 << numlock),
+                        modifiers.group
+                    )
+                }
+            }
 
             self.wlEventHandler.addKeyboardListeners(device: device)
 
@@ -447,10 +547,9 @@ extension Awc {
             wlr_seat_set_keyboard(self.seat, device)
             self.hasKeyboard = true
         } else if device.pointee.type == WLR_INPUT_DEVICE_POINTER {
-            // We don't do anything special with pointers. All of our pointer handling
-            // is proxied through wlr_cursor. On another compositor, you might take this
-            // opportunity to do libinput configuration on the device to set
-            // acceleration, etc.
+            // All of our pointer handling is proxied through wlr_cursor. libinput
+            // settings such as acceleration are configured here.
+            self.configureInput(device)
             wlr_cursor_attach_input_device(self.cursor, device)
 
             if let deviceOutputName = device.pointee.output_name,
@@ -470,6 +569,28 @@ extension Awc {
         wlr_seat_set_capabilities(self.seat, caps)
     }
 
+    /// Returns the output's mode matching the configured mode. If no refresh rate is configured, the mode with the
+    /// highest refresh rate is picked.
+    private func findMode(
+        _ wlrOutput: UnsafeMutablePointer<wlr_output>,
+        _ configured: AwcOutputMode
+    ) -> UnsafeMutablePointer<wlr_output_mode>? {
+        guard configured.width > 0 else {
+            return nil
+        }
+        let mode = wlrOutput.pointee.modes.sequence(\wlr_output_mode.link)
+            .filter {
+                $0.pointee.width == configured.width && $0.pointee.height == configured.height
+                    // Refresh rates are in mHz, allow for some rounding
+                    && (configured.refresh == 0 || abs($0.pointee.refresh - configured.refresh) <= 1)
+            }
+            .max(by: { $0.pointee.refresh < $1.pointee.refresh })
+        if mode == nil {
+            logger.warning("Output \(wlrOutput.name) doesn't support the configured mode, using preferred mode")
+        }
+        return mode
+    }
+
     private func handleNewOutput(_ wlrOutput: UnsafeMutablePointer<wlr_output>) {
 #if !WLROOTS_0_14
         guard wlr_output_init_render(wlrOutput, self.allocator, self.renderer) else {
@@ -478,37 +599,105 @@ extension Awc {
         }
 #endif
 
-        let name = wlrOutput.name
+        self.wlEventHandler.addOutputListeners(output: wlrOutput)
+        self.connectedOutputs.append(wlrOutput)
+        self.configureOutputs()
+
+        // Show a cursor
+        wlr_xcursor_manager_set_cursor_image(self.cursorManager, "left_ptr", self.cursor)
+    }
 
-        if let (_, _, scale) = self.config.outputConfigs[name] {
-            wlr_output_set_scale(wlrOutput, scale)
+    /// Applies the output configs (or the matching profile) to all connected outputs. Outputs are enabled and
+    /// disabled as configured. Only outputs that are new or whose config changed are committed, so that the other
+    /// outputs don't flicker.
+    internal func configureOutputs() {
+        let outputConfigs = self.config.outputConfigs(for: self.connectedOutputs)
+        for (wlrOutput, outputConfig) in zip(self.connectedOutputs, outputConfigs) {
+            let isEnabled = self.viewSet.outputs().contains(where: { $0.data.output == wlrOutput })
+            if let outputConfig = outputConfig, !outputConfig.enabled {
+                if isEnabled {
+                    self.removeOutput(wlrOutput, disable: true)
+                }
+                if wlrOutput.pointee.enabled {
+                    logger.info("Output \(wlrOutput.name) is disabled by config")
+                    wlr_output_enable(wlrOutput, false)
+                    wlr_output_commit(wlrOutput)
+                }
+            } else if !isEnabled {
+                if self.configure(wlrOutput, outputConfig) {
+                    self.addOutput(wlrOutput, outputConfig)
+                }
+            } else if self.isConfigured(wlrOutput, outputConfig) || self.configure(wlrOutput, outputConfig) {
+                if let outputConfig = outputConfig,
+                    let layoutOutput = wlr_output_layout_get(self.outputLayout, wlrOutput),
+                    layoutOutput.pointee.x != outputConfig.x || layoutOutput.pointee.y != outputConfig.y
+                {
+                    wlr_output_layout_move(self.outputLayout, wlrOutput, outputConfig.x, outputConfig.y)
+                }
+            }
         }
+    }
+
+    /// Returns whether the output is enabled and already has the scale, transform, adaptive sync and mode that
+    /// `configure` would set.
+    private func isConfigured(_ wlrOutput: UnsafeMutablePointer<wlr_output>, _ outputConfig: AwcOutputConfig?) -> Bool {
+        let output = wlrOutput.pointee
+        let adaptiveSync = output.adaptive_sync_status == WLR_OUTPUT_ADAPTIVE_SYNC_ENABLED
+        guard output.enabled,
+            output.scale == outputConfig?.scale ?? 1.0,
+            output.transform == self.configuredTransform(outputConfig),
+            adaptiveSync == outputConfig?.adaptive_sync ?? false
+        else {
+            return false
+        }
+        if wl_list_empty(&wlrOutput.pointee.modes) == 0 {
+            return output.current_mode == self.configuredMode(wlrOutput, outputConfig)
+        } else if let mode = outputConfig?.mode, mode.width > 0 {
+            return output.width == mode.width && output.height == mode.height
+                && (mode.refresh == 0 || output.refresh == mode.refresh)
+        }
+        return true
+    }
+
+    /// Sets the output's scale, transform, adaptive sync and mode and enables it. Without a config, the defaults of
+    /// `Output` in Types.dhall are used. Returns whether that succeeded.
+    private func configure(_ wlrOutput: UnsafeMutablePointer<wlr_output>, _ outputConfig: AwcOutputConfig?) -> Bool {
+        wlr_output_set_scale(wlrOutput, outputConfig?.scale ?? 1.0)
+        wlr_output_set_transform(wlrOutput, self.configuredTransform(outputConfig))
+        wlr_output_enable_adaptive_sync(wlrOutput, outputConfig?.adaptive_sync ?? false)
 
         // Some backends don't have modes. DRM+KMS does, and we need to set a mode
         // before we can use the output. The mode is a tuple of (width, height,
-        // refresh rate), and each monitor supports only a specific set of modes. We
-        // just pick the monitor's preferred mode, a more sophisticated compositor
-        // would let the user configure it.
+        // refresh rate), and each monitor supports only a specific set of modes.
         if wl_list_empty(&wlrOutput.pointee.modes) == 0 {
-            let mode = wlr_output_preferred_mode(wlrOutput)
-            wlr_output_set_mode(wlrOutput, mode)
-            wlr_output_enable(wlrOutput, true)
-            guard wlr_output_commit(wlrOutput) else {
-                return
-            }
+            wlr_output_set_mode(wlrOutput, self.configuredMode(wlrOutput, outputConfig))
+        } else if let mode = outputConfig?.mode, mode.width > 0 {
+            wlr_output_set_custom_mode(wlrOutput, mode.width, mode.height, mode.refresh)
         }
+        wlr_output_enable(wlrOutput, true)
+        return wlr_output_commit(wlrOutput)
+    }
 
-        self.wlEventHandler.addOutputListeners(output: wlrOutput)
+    /// Returns the configured mode if the monitor supports it and the monitor's preferred mode otherwise.
+    private func configuredMode(
+        _ wlrOutput: UnsafeMutablePointer<wlr_output>,
+        _ outputConfig: AwcOutputConfig?
+    ) -> UnsafeMutablePointer<wlr_output_mode>? {
+        outputConfig.flatMap { self.findMode(wlrOutput, $0.mode) } ?? wlr_output_preferred_mode(wlrOutput)
+    }
+
+    private func configuredTransform(_ outputConfig: AwcOutputConfig?) -> wl_output_transform {
+        wl_output_transform(rawValue: outputConfig?.transform.rawValue ?? WL_OUTPUT_TRANSFORM_NORMAL.rawValue)
+    }
 
+    private func addOutput(_ wlrOutput: UnsafeMutablePointer<wlr_output>, _ outputConfig: AwcOutputConfig?) {
         // Adds this to the output layout. The add_auto function arranges outputs
-        // from left-to-right in the order they appear. A more sophisticated
-        // compositor would let the user configure the arrangement of outputs in th
-        // layout.
+        // from left-to-right in the order they appear.
         // The output layout utility automatically adds a wl_output global to the
         // display, which Wayland clients can see to find out information about the
         // output (such as DPI, scale factor, manufacturer, etc).
-        if let (x, y, _) = self.config.outputConfigs[name] {
-            wlr_output_layout_add(self.outputLayout, wlrOutput, x, y)
+        if let outputConfig = outputConfig {
+            wlr_output_layout_add(self.outputLayout, wlrOutput, outputConfig.x, outputConfig.y)
         } else {
             wlr_output_layout_add_auto(self.outputLayout, wlrOutput)
         }
@@ -528,12 +717,15 @@ extension Awc {
                 )
                 return $0.replace(current: newOutput)
             } else {
+                let data = OutputDetails(wlrOutput: wlrOutput, outputLayout: self.outputLayout, damage: damage)
+                // Prefer a workspace that is configured for this output
+                let outputNumber = 1 + $0.outputs().filter { $0.data.box.x < data.box.x }.count
                 var hidden = Array(self.viewSet.hidden)
-                if let workspace = hidden.popLast() {
-                    let newOutput = Output(
-                        data: OutputDetails(wlrOutput: wlrOutput, outputLayout: self.outputLayout, damage: damage),
-                        workspace: workspace
-                    )
+                let preferred = hidden.lastIndex(where: {
+                    self.config.workspace(tag: $0.tag)?.output == UInt8(clamping: outputNumber)
+                })
+                if let workspace = preferred.map({ hidden.remove(at: $0) }) ?? hidden.popLast() {
+                    let newOutput = Output(data: data, workspace: workspace)
                     return $0.replace(
                             current: newOutput,
                             visible: Array(self.viewSet.visible) + [self.viewSet.current],
@@ -544,9 +736,6 @@ extension Awc {
                 }
             }
         }
-
-        // Show a cursor
-        wlr_xcursor_manager_set_cursor_image(self.cursorManager, "left_ptr", self.cursor)
     }
 
     private func handleOutputDestroyed(_ wlrOutput: UnsafeMutablePointer<wlr_output>) {
@@ -555,9 +744,25 @@ extension Awc {
         }
 
         self.wlEventHandler.removeOutputListeners(output: wlrOutput)
+        self.connectedOutputs.removeAll(where: { $0 == wlrOutput })
 
+        if self.viewSet.outputs().contains(where: { $0.data.output == wlrOutput }) {
+            self.removeOutput(wlrOutput, disable: false)
+        }
+
+        // A different profile might match the remaining outputs
+        self.configureOutputs()
+    }
+
+    /// Removes the output from the view set. If the output is only disabled (and not destroyed), it's also removed
+    /// from the output layout.
+    private func removeOutput(_ wlrOutput: UnsafeMutablePointer<wlr_output>, disable: Bool) {
         if let output = self.viewSet.outputs().first(where: { $0.data.output == wlrOutput }) {
             self.removeListener(output.data.damage, OutputDamageListener.self)
+            if disable {
+                wlr_output_damage_destroy(output.data.damage)
+                wlr_output_layout_remove(self.outputLayout, wlrOutput)
+            }
         }
 
         self.modifyAndUpdate {
@@ -587,7 +792,7 @@ extension Awc {
                         hidden: $0.hidden + [output.workspace]
                 )
             } else {
-                logger.warning("Got 'output destroyed' event for some unknown output o_O")
+                logger.warning("Tried to remove some unknown output o_O")
                 return $0
             }
         }
@@ -632,6 +837,7 @@ extension Awc {
             if event.pointee.state == WL_KEYBOARD_KEY_STATE_PRESSED {
                 for i in 0..<Int(nsyms) {
                     if let action = self.config.findKeyBinding(
+                        mode: self.mode,
                         modifiers: modifiers,
                         code: keycode,
                         sym: syms[i]!.pointee
@@ -861,7 +1067,9 @@ func main() {
     initLogging(level: args.debug ? .debug : .info)
     logger = Logger(label: "awc")
 
-    guard let config = loadConfig(path: args.configPath) else {
+    let (maybeConfig, configDiagnostics) =
+        loadConfig(path: args.configPath, forbidRemoteImports: args.noRemoteImports)
+    guard let config = maybeConfig else {
         logger.critical("Could not load configuration")
         return
     }
@@ -1006,9 +1214,14 @@ func main() {
         return
     }
 
-    typealias LayerAndBorderLayout = LayerLayout<BorderShrinkLayout<AnyLayout<Surface, OutputDetails>>>
-    let layoutWrapper: (AnyLayout<Surface, OutputDetails>) -> LayerAndBorderLayout =
-        { LayerLayout(wrapped: BorderShrinkLayout(borderWidth: config.borderWidth, layout: $0)) }
+    typealias LayerAndBorderLayout =
+        LayerLayout<BorderShrinkLayout<TabStripLayout<AnyLayout<Surface, OutputDetails>>>>
+    let tabHeight = Int32(clamping: config.tabs.height)
+    let layoutWrapper: (AnyLayout<Surface, OutputDetails>) -> LayerAndBorderLayout = {
+        LayerLayout(wrapped: BorderShrinkLayout(
+            borderWidth: config.borderWidth,
+            layout: TabStripLayout(height: tabHeight, layout: $0)))
+    }
     let awc = Awc(
         wlEventHandler: wlEventHandler,
         wlDisplay: wlDisplay,
@@ -1027,7 +1240,7 @@ func main() {
             borderWidth: config.borderWidth,
             activeBorderColor: config.colors.borders.active.toFloatRgba(),
             inactiveBorderColor: config.colors.borders.inactive.toFloatRgba(),
-            renderSurface
+            tabStrips(height: config.tabs.height, font: config.font, colors: config.tabs, renderSurface)
         ),
         viewAtHook: { layerViewAt(delegate: defaultViewAtHook, awc: $0, x: $1, y: $2) },
         config: config
@@ -1052,6 +1265,10 @@ func main() {
 
     runAutostart()
 
+    if !configDiagnostics.isEmpty {
+        awc.displayConfigDiagnostics("Loaded config with warnings:", configDiagnostics, using: config)
+    }
+
     // Run the Wayland event loop. This does not return until you exit the
     // compositor. Starting the backend rigged up all of the necessary event
     // loop configuration to listen to libinput events, DRM events, generate
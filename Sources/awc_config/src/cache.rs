@@ -0,0 +1,50 @@
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+use crate::imports::write_atomically;
+use crate::Config;
+
+/// Part of every key, together with the crate version. Bump it whenever the
+/// cached structures or how they are (de)serialized change, so that configs
+/// cached by an older awc aren't misread.
+const SCHEMA_VERSION: u32 = 17;
+
+/// Returns the cached config for the config file at `config_path`, if it was
+/// cached with the same fingerprint.
+pub(crate) fn load(config_path: &Path, fingerprint: u64) -> Option<Config> {
+    let bytes = fs::read(cache_path(config_path)?).ok()?;
+    match bincode::deserialize::<(u64, Config)>(&bytes) {
+        Ok((key, config)) if key == cache_key(fingerprint) => Some(config),
+        _ => None,
+    }
+}
+
+/// Caches `config` for the config file at `config_path`. Failures are
+/// ignored, the config is then simply evaluated again next time.
+pub(crate) fn store(config_path: &Path, fingerprint: u64, config: &Config) {
+    if let (Some(path), Ok(bytes)) = (
+        cache_path(config_path),
+        bincode::serialize(&(cache_key(fingerprint), config)),
+    ) {
+        let _ = write_atomically(&path, bytes);
+    }
+}
+
+fn cache_key(fingerprint: u64) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    SCHEMA_VERSION.hash(&mut hasher);
+    env!("CARGO_PKG_VERSION").hash(&mut hasher);
+    fingerprint.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn cache_path(config_path: &Path) -> Option<PathBuf> {
+    let mut hasher = DefaultHasher::new();
+    config_path.hash(&mut hasher);
+    xdg::BaseDirectories::with_prefix("awc")
+        .ok()?
+        .place_cache_file(format!("configs/{:016x}.bin", hasher.finish()))
+        .ok()
+}
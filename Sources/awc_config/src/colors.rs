@@ -0,0 +1,131 @@
+use std::fmt;
+
+use serde::de::{self, Deserializer, MapAccess, Visitor};
+use serde::Deserialize;
+
+use crate::AwcColor;
+
+/// Colors that can be referred to by name.
+const NAMED_COLORS: &[(&str, AwcColor)] = &[
+    ("black", rgba(0x00, 0x00, 0x00, 0xff)),
+    ("white", rgba(0xff, 0xff, 0xff, 0xff)),
+    ("gray", rgba(0x80, 0x80, 0x80, 0xff)),
+    ("red", rgba(0xff, 0x00, 0x00, 0xff)),
+    ("green", rgba(0x00, 0xff, 0x00, 0xff)),
+    ("blue", rgba(0x00, 0x00, 0xff, 0xff)),
+    ("yellow", rgba(0xff, 0xff, 0x00, 0xff)),
+    ("cyan", rgba(0x00, 0xff, 0xff, 0xff)),
+    ("magenta", rgba(0xff, 0x00, 0xff, 0xff)),
+    ("transparent", rgba(0x00, 0x00, 0x00, 0x00)),
+];
+
+const fn rgba(r: u8, g: u8, b: u8, a: u8) -> AwcColor {
+    AwcColor { r, g, b, a }
+}
+
+#[derive(Deserialize)]
+struct Rgba<T> {
+    r: T,
+    g: T,
+    b: T,
+    a: T,
+}
+
+impl Rgba<u64> {
+    fn to_color(&self) -> Result<AwcColor, String> {
+        let component = |name: &str, value: u64| {
+            if value <= 0xff {
+                Ok(value as u8)
+            } else {
+                Err(format!(
+                    "color component `{}` is {}, but must be between 0 and 255",
+                    name, value
+                ))
+            }
+        };
+        Ok(rgba(
+            component("r", self.r)?,
+            component("g", self.g)?,
+            component("b", self.b)?,
+            component("a", self.a)?,
+        ))
+    }
+}
+
+/// Parses `#rrggbb` or `#rrggbbaa`.
+fn parse_hex(value: &str) -> Result<AwcColor, String> {
+    let invalid = || {
+        format!(
+            "invalid color `{}`, expected `#rrggbb` or `#rrggbbaa`",
+            value
+        )
+    };
+    let digits = value.strip_prefix('#').ok_or_else(invalid)?;
+    if !(digits.len() == 6 || digits.len() == 8) || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(invalid());
+    }
+    let component = |i: usize| u8::from_str_radix(&digits[i..i + 2], 16).unwrap();
+    let a = if digits.len() == 8 {
+        component(6)
+    } else {
+        0xff
+    };
+    Ok(rgba(component(0), component(2), component(4), a))
+}
+
+fn parse_named(value: &str) -> Result<AwcColor, String> {
+    NAMED_COLORS
+        .iter()
+        .find(|(name, _)| *name == value)
+        .map(|(_, color)| *color)
+        .ok_or_else(|| {
+            let names = NAMED_COLORS
+                .iter()
+                .map(|(name, _)| *name)
+                .collect::<Vec<&str>>();
+            format!(
+                "unknown color name `{}`, expected one of {}",
+                value,
+                names.join(", ")
+            )
+        })
+}
+
+fn parse(value: &str) -> Result<AwcColor, String> {
+    if value.starts_with('#') {
+        parse_hex(value)
+    } else {
+        parse_named(value)
+    }
+}
+
+struct ColorVisitor;
+
+impl<'de> Visitor<'de> for ColorVisitor {
+    type Value = AwcColor;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an RGBA record, a `#rrggbb[aa]` string or a color name")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<AwcColor, E> {
+        parse(value).map_err(E::custom)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<AwcColor, A::Error> {
+        let components = Rgba::<u64>::deserialize(de::value::MapAccessDeserializer::new(map))?;
+        components.to_color().map_err(de::Error::custom)
+    }
+}
+
+impl<'de> Deserialize<'de> for AwcColor {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<AwcColor, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(ColorVisitor)
+        } else {
+            // Cached configs store colors as they are serialized
+            let Rgba { r, g, b, a } = Rgba::<u8>::deserialize(deserializer)?;
+            Ok(rgba(r, g, b, a))
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use crate::Config;
+
+/// What changed between two configs. Indices refer to the old config for
+/// removed things and to the new config for everything else.
+#[derive(Debug)]
+pub(crate) struct ConfigDiff {
+    pub added_button_bindings: Vec<usize>,
+    pub removed_button_bindings: Vec<usize>,
+    pub added_gesture_bindings: Vec<usize>,
+    pub removed_gesture_bindings: Vec<usize>,
+    pub added_key_bindings: Vec<usize>,
+    pub removed_key_bindings: Vec<usize>,
+    pub added_outputs: Vec<usize>,
+    pub removed_outputs: Vec<usize>,
+    pub changed_outputs: Vec<usize>,
+    pub added_workspaces: Vec<usize>,
+    pub removed_workspaces: Vec<usize>,
+    pub changed_workspaces: Vec<usize>,
+    /// Pairs of old and new index
+    pub renamed_workspaces: Vec<(usize, usize)>,
+    pub border_width_changed: bool,
+    pub colors_changed: bool,
+    pub display_error_cmd_changed: bool,
+    pub font_changed: bool,
+    pub inputs_changed: bool,
+    pub keyboards_changed: bool,
+    pub layout_changed: bool,
+    pub modes_changed: bool,
+    pub modifier_changed: bool,
+    pub profiles_changed: bool,
+    pub tabs_changed: bool,
+    pub window_rules_changed: bool,
+}
+
+pub(crate) fn diff(old: &Config, new: &Config) -> ConfigDiff {
+    let (old_outputs, new_outputs) = (old.output_configs(), new.output_configs());
+    let (added_outputs, removed_outputs) =
+        added_and_removed_by(&old_outputs, &new_outputs, |o| &o.matches);
+    let changed_outputs = changed_by(&old_outputs, &new_outputs, |o| &o.matches);
+
+    let (mut added_workspaces, mut removed_workspaces) =
+        added_and_removed(&old.workspaces, &new.workspaces);
+    let changed_workspaces = new
+        .workspaces
+        .iter()
+        .enumerate()
+        .filter(|(_, tag)| {
+            old.workspaces.contains(tag)
+                && old.workspace_settings(tag) != new.workspace_settings(tag)
+        })
+        .map(|(i, _)| i)
+        .collect();
+    // A workspace that disappeared at the same position where a new one
+    // appeared has been renamed
+    let renamed_workspaces = removed_workspaces
+        .iter()
+        .copied()
+        .filter(|i| added_workspaces.contains(i))
+        .map(|i| (i, i))
+        .collect::<Vec<(usize, usize)>>();
+    for (old_index, new_index) in &renamed_workspaces {
+        removed_workspaces.retain(|i| i != old_index);
+        added_workspaces.retain(|i| i != new_index);
+    }
+
+    let (added_button_bindings, removed_button_bindings) =
+        added_and_removed(&old.button_bindings, &new.button_bindings);
+    let (added_gesture_bindings, removed_gesture_bindings) =
+        added_and_removed(&old.gesture_bindings, &new.gesture_bindings);
+    let (added_key_bindings, removed_key_bindings) =
+        added_and_removed(&old.key_bindings, &new.key_bindings);
+
+    ConfigDiff {
+        added_button_bindings,
+        removed_button_bindings,
+        added_gesture_bindings,
+        removed_gesture_bindings,
+        added_key_bindings,
+        removed_key_bindings,
+        added_outputs,
+        removed_outputs,
+        changed_outputs,
+        added_workspaces,
+        removed_workspaces,
+        changed_workspaces,
+        renamed_workspaces,
+        border_width_changed: old.border_width != new.border_width,
+        colors_changed: old.colors != new.colors,
+        display_error_cmd_changed: old.display_error_cmd != new.display_error_cmd,
+        font_changed: old.font != new.font,
+        inputs_changed: old.inputs != new.inputs,
+        keyboards_changed: old.keyboards != new.keyboards
+            || old.keyboard_settings != new.keyboard_settings,
+        layout_changed: old.layout != new.layout,
+        modes_changed: old.modes != new.modes,
+        modifier_changed: old.modifier != new.modifier,
+        profiles_changed: old.profiles != new.profiles,
+        tabs_changed: old.tabs != new.tabs,
+        window_rules_changed: old.window_rules != new.window_rules,
+    }
+}
+
+/// Returns the indices of the elements only in `new` and of those only in
+/// `old`.
+fn added_and_removed<T: PartialEq>(old: &[T], new: &[T]) -> (Vec<usize>, Vec<usize>) {
+    added_and_removed_by(old, new, |x| x)
+}
+
+/// Returns the indices of the elements in `new` that have a different
+/// counterpart with the same key in `old`.
+fn changed_by<T: PartialEq, K: PartialEq + ?Sized, F: Fn(&T) -> &K>(
+    old: &[T],
+    new: &[T],
+    key: F,
+) -> Vec<usize> {
+    new.iter()
+        .enumerate()
+        .filter(|(_, x)| old.iter().any(|o| key(o) == key(x) && o != *x))
+        .map(|(i, _)| i)
+        .collect()
+}
+
+fn added_and_removed_by<T, K: PartialEq + ?Sized, F: Fn(&T) -> &K>(
+    old: &[T],
+    new: &[T],
+    key: F,
+) -> (Vec<usize>, Vec<usize>) {
+    let only_in = |xs: &[T], others: &[T]| {
+        xs.iter()
+            .enumerate()
+            .filter(|(_, x)| !others.iter().any(|other| key(other) == key(x)))
+            .map(|(i, _)| i)
+            .collect::<Vec<usize>>()
+    };
+    (only_in(new, old), only_in(old, new))
+}
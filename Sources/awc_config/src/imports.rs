@@ -0,0 +1,870 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::env;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::ops::Range;
+use std::path::{Component, Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+const TYPES: &str = include_str!("../Dhall/Types.dhall");
+
+/// The environment variable configs traditionally import the types from.
+const TYPES_ENV_VAR: &str = "AWC_TYPES";
+
+static TMP_FILE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Resolves the `env:AWC_TYPES` imports of a config without touching the
+/// process environment.
+///
+/// `Types.dhall` is installed as `$XDG_DATA_HOME/awc/Types.dhall`, which
+/// configs can also import directly. The config and all files it imports
+/// with relative paths are staged as copies below
+/// `$XDG_CACHE_HOME/awc/imports`, in a tree that mirrors where the originals
+/// are, so that relative imports resolve to staged copies as well. The copies
+/// only differ from the originals in that `env:AWC_TYPES` is replaced by the
+/// installed file. Files imported with absolute or home paths are loaded by
+/// Dhall as usual.
+///
+/// Remote imports are left to Dhall, but are recorded so that failures to
+/// fetch them can be explained. They can also be forbidden altogether.
+///
+/// Everything the result of evaluating the config depends on is fed into a
+/// fingerprint along the way.
+pub(crate) struct Resolver {
+    types_path: PathBuf,
+    config_path: PathBuf,
+    staging_dir: PathBuf,
+    /// The originals of all staged files
+    staged: HashSet<PathBuf>,
+    /// Files that aren't staged, but were scanned for imports
+    visited: HashSet<PathBuf>,
+    forbid_remote_imports: bool,
+    remote_imports: Vec<(String, PathBuf)>,
+    fingerprint: DefaultHasher,
+    fingerprintable: bool,
+}
+
+impl Resolver {
+    pub fn new(config_path: &Path, forbid_remote_imports: bool) -> Result<Resolver, String> {
+        let xdg_dirs = xdg::BaseDirectories::with_prefix("awc")
+            .map_err(|e| format!("cannot determine XDG directories: {}", e))?;
+
+        let types_path = xdg_dirs
+            .place_data_file("Types.dhall")
+            .map_err(|e| format!("cannot create data directory: {}", e))?;
+        if fs::read_to_string(&types_path).ok().as_deref() != Some(TYPES) {
+            write_atomically(&types_path, TYPES)?;
+        }
+
+        let mut fingerprint = DefaultHasher::new();
+        TYPES.hash(&mut fingerprint);
+
+        // Made absolute without resolving symlinks, relative imports are
+        // resolved the same way by Dhall
+        let current_dir =
+            env::current_dir().map_err(|e| format!("cannot determine current directory: {}", e))?;
+        let config_path = normalize(&current_dir.join(config_path));
+
+        let mut hasher = DefaultHasher::new();
+        config_path.hash(&mut hasher);
+        let staging_dir = xdg_dirs
+            .create_cache_directory(format!("imports/{:016x}", hasher.finish()))
+            .map_err(|e| format!("cannot create cache directory: {}", e))?;
+
+        Ok(Resolver {
+            types_path,
+            config_path,
+            staging_dir,
+            staged: HashSet::new(),
+            visited: HashSet::new(),
+            forbid_remote_imports,
+            remote_imports: Vec::new(),
+            fingerprint,
+            fingerprintable: true,
+        })
+    }
+
+    /// Stages the config and everything it imports with relative paths.
+    /// Staged copies of files the config doesn't import anymore are removed.
+    /// Returns the path of the staged config, which can be loaded as usual.
+    pub fn stage(&mut self) -> Result<PathBuf, String> {
+        let config_path = self.config_path.clone();
+        self.stage_file(&config_path)?;
+        self.remove_stale(&self.staging_dir);
+        Ok(self.staged_path(&config_path))
+    }
+
+    /// Returns a fingerprint of everything staged so far, or `None` if it
+    /// depends on something that can't be fingerprinted (i.e. a remote
+    /// import that isn't protected by a hash).
+    pub fn fingerprint(&self) -> Option<u64> {
+        if self.fingerprintable {
+            Some(self.fingerprint.finish())
+        } else {
+            None
+        }
+    }
+
+    /// Makes a Dhall error message about the staged files understandable:
+    /// paths of staged copies are replaced with the paths of their originals
+    /// and remote imports that couldn't be fetched are pointed out.
+    pub fn explain_error(&self, message: &str) -> String {
+        let message = message.replace(&self.staging_dir.display().to_string(), "");
+        if let Some((url, file)) = self
+            .remote_imports
+            .iter()
+            .find(|(url, _)| message.contains(url.as_str()))
+        {
+            format!(
+                "cannot fetch remote import {} (imported by {}), is the network \
+                 available? Make it local or protect it with a sha256 hash that \
+                 is in the Dhall cache\n{}",
+                url,
+                file.display(),
+                message
+            )
+        } else if message.contains(TYPES_ENV_VAR) {
+            format!(
+                "{}\nenv:{} is only resolved in the config and the files it imports with \
+                 relative paths, other files can import {}",
+                message,
+                TYPES_ENV_VAR,
+                self.types_path.display()
+            )
+        } else {
+            message
+        }
+    }
+
+    fn stage_file(&mut self, path: &Path) -> Result<(), String> {
+        // Registered before staging imports, so that import cycles terminate
+        // (and are then reported by Dhall)
+        if !self.staged.insert(path.to_path_buf()) {
+            return Ok(());
+        }
+
+        let source = fs::read_to_string(path)
+            .map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+        path.hash(&mut self.fingerprint);
+        source.hash(&mut self.fingerprint);
+        let imports = self.scan_file(path, &source)?;
+
+        let mut staged = String::with_capacity(source.len());
+        let mut copied = 0;
+        for import in imports {
+            match import.kind {
+                ImportKind::Env(name) if name == TYPES_ENV_VAR => {
+                    staged.push_str(&source[copied..import.range.start]);
+                    staged.push_str(&dhall_path(&self.types_path));
+                    copied = import.range.end;
+                }
+                // Missing files are left to Dhall, which might fall back to
+                // an alternative import
+                ImportKind::Local {
+                    path: target,
+                    relative: true,
+                    code,
+                } if target.is_file() => {
+                    if code {
+                        self.stage_file(&target)?;
+                    } else {
+                        self.stage_raw_file(&target)?;
+                    }
+                }
+                ImportKind::Local {
+                    path: target, code, ..
+                } => self.visit(&target, code)?,
+                _ => (),
+            }
+        }
+        staged.push_str(&source[copied..]);
+
+        write_if_changed(&self.staged_path(path), staged.as_bytes())
+    }
+
+    /// Stages a file that is imported as something else than Dhall code,
+    /// which is copied as it is.
+    fn stage_raw_file(&mut self, path: &Path) -> Result<(), String> {
+        if !self.staged.insert(path.to_path_buf()) {
+            return Ok(());
+        }
+        let contents =
+            fs::read(path).map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+        path.hash(&mut self.fingerprint);
+        contents.hash(&mut self.fingerprint);
+        write_if_changed(&self.staged_path(path), &contents)
+    }
+
+    /// Scans and fingerprints a file that isn't staged because it is imported
+    /// with an absolute or home path, and everything it imports.
+    fn visit(&mut self, path: &Path, code: bool) -> Result<(), String> {
+        if !self.visited.insert(path.to_path_buf()) {
+            return Ok(());
+        }
+        path.hash(&mut self.fingerprint);
+        let source = match fs::read(path) {
+            Ok(contents) => {
+                contents.hash(&mut self.fingerprint);
+                String::from_utf8(contents).ok()
+            }
+            // Missing files are left to Dhall
+            Err(_) => None,
+        };
+        if let (true, Some(source)) = (code, source) {
+            for import in self.scan_file(path, &source)? {
+                if let ImportKind::Local {
+                    path: target, code, ..
+                } = import.kind
+                {
+                    self.visit(&target, code)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Finds the imports in `source`, the contents of the file at `path`.
+    /// Remote imports are checked and recorded and environment variables
+    /// fingerprinted along the way.
+    fn scan_file(&mut self, path: &Path, source: &str) -> Result<Vec<Import>, String> {
+        let dir = path.parent().unwrap_or_else(|| Path::new("/"));
+        let imports = scan(source, dir).map_err(|e| format!("{}: {}", path.display(), e))?;
+        for import in &imports {
+            match &import.kind {
+                ImportKind::Env(name) => {
+                    name.hash(&mut self.fingerprint);
+                    env::var_os(name).hash(&mut self.fingerprint);
+                }
+                ImportKind::Remote(url) => {
+                    if self.forbid_remote_imports {
+                        return Err(format!(
+                            "{}: remote import {} is forbidden",
+                            path.display(),
+                            url
+                        ));
+                    }
+                    self.remote_imports.push((url.clone(), path.to_path_buf()));
+                    if source[import.range.end..]
+                        .trim_start()
+                        .starts_with("sha256:")
+                    {
+                        url.hash(&mut self.fingerprint);
+                    } else {
+                        self.fingerprintable = false;
+                    }
+                }
+                ImportKind::Local { .. } => (),
+            }
+        }
+        Ok(imports)
+    }
+
+    fn staged_path(&self, path: &Path) -> PathBuf {
+        self.staging_dir
+            .join(path.strip_prefix("/").unwrap_or(path))
+    }
+
+    /// Removes everything below `dir` that isn't a staged copy. Returns
+    /// whether `dir` is empty afterwards.
+    fn remove_stale(&self, dir: &Path) -> bool {
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(_) => return false,
+        };
+        let mut empty = true;
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if entry.file_type().map(|t| t.is_dir()).unwrap_or(false) {
+                if self.remove_stale(&path) {
+                    let _ = fs::remove_dir(&path);
+                } else {
+                    empty = false;
+                }
+            } else if self.is_stale(&path) {
+                let _ = fs::remove_file(&path);
+            } else {
+                empty = false;
+            }
+        }
+        empty
+    }
+
+    fn is_stale(&self, staged_path: &Path) -> bool {
+        // Might be written by another process that loads the same config
+        let is_being_written = staged_path
+            .extension()
+            .map(|e| e.to_string_lossy().starts_with("tmp-"))
+            .unwrap_or(false);
+        match staged_path.strip_prefix(&self.staging_dir) {
+            Ok(original) => {
+                !is_being_written && !self.staged.contains(&Path::new("/").join(original))
+            }
+            Err(_) => false,
+        }
+    }
+}
+
+pub(crate) fn write_atomically<C: AsRef<[u8]>>(path: &Path, contents: C) -> Result<(), String> {
+    let tmp_path = path.with_extension(format!(
+        "tmp-{}-{}",
+        process::id(),
+        TMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    fs::write(&tmp_path, contents)
+        .and_then(|_| fs::rename(&tmp_path, path))
+        .map_err(|e| {
+            let _ = fs::remove_file(&tmp_path);
+            format!("cannot write {}: {}", path.display(), e)
+        })
+}
+
+/// Like `write_atomically`, but leaves the file alone if it already has the
+/// right contents. Creates missing parent directories.
+fn write_if_changed(path: &Path, contents: &[u8]) -> Result<(), String> {
+    if fs::read(path).ok().as_deref() == Some(contents) {
+        return Ok(());
+    }
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .map_err(|e| format!("cannot create directory {}: {}", dir.display(), e))?;
+    }
+    write_atomically(path, contents)
+}
+
+/// An import in a Dhall file.
+#[derive(Debug, PartialEq)]
+struct Import {
+    /// Where the import is in the file, without a hash or `as …`
+    range: Range<usize>,
+    kind: ImportKind,
+}
+
+#[derive(Debug, PartialEq)]
+enum ImportKind {
+    Env(String),
+    Remote(String),
+    Local {
+        /// Absolute, with `.` and `..` resolved
+        path: PathBuf,
+        relative: bool,
+        /// Whether the file is imported as Dhall code
+        code: bool,
+    },
+}
+
+/// Finds the imports in `source`, a Dhall file in `dir`. Only knows enough of
+/// Dhall's syntax to tell imports apart from comments, text literals and
+/// quoted labels.
+fn scan(source: &str, dir: &Path) -> Result<Vec<Import>, String> {
+    let mut imports = Vec::new();
+    scan_expression(source, 0, dir, false, &mut imports)?;
+    Ok(imports)
+}
+
+/// Scans the expression starting at `start`. Inside an interpolation, stops
+/// at the `}` that closes it. Returns where it stopped.
+fn scan_expression(
+    source: &str,
+    start: usize,
+    dir: &Path,
+    interpolation: bool,
+    imports: &mut Vec<Import>,
+) -> Result<usize, String> {
+    let bytes = source.as_bytes();
+    let mut depth = 0usize;
+    let mut i = start;
+
+    while i < bytes.len() {
+        let rest = &source[i..];
+        let at_token_start = i == 0 || !is_label_char(bytes[i - 1]);
+
+        i = if rest.starts_with("--") {
+            i + rest.find('\n').unwrap_or(rest.len())
+        } else if rest.starts_with("{-") {
+            i + block_comment_len(rest)
+        } else if rest.starts_with('"') || rest.starts_with("''") {
+            scan_literal(source, i, dir, imports)?
+        } else if let Some(label) = rest.strip_prefix('`') {
+            i + 1 + label.find('`').map(|n| n + 1).unwrap_or(label.len())
+        } else if rest.starts_with("//") {
+            // The `//` operator, not the start of an absolute path
+            i + 2
+        } else if rest.starts_with('{') {
+            depth += 1;
+            i + 1
+        } else if rest.starts_with('}') {
+            if interpolation && depth == 0 {
+                return Ok(i);
+            }
+            depth = depth.saturating_sub(1);
+            i + 1
+        } else if at_token_start && rest.starts_with("env:") {
+            let (name, len) = env_var_name(&rest["env:".len()..]);
+            let end = i + "env:".len() + len;
+            imports.push(Import {
+                range: i..end,
+                kind: ImportKind::Env(name.to_string()),
+            });
+            end
+        } else if at_token_start && (rest.starts_with("http://") || rest.starts_with("https://")) {
+            let url = &rest[..rest
+                .find(|c: char| c.is_whitespace() || c == ')')
+                .unwrap_or(rest.len())];
+            imports.push(Import {
+                range: i..i + url.len(),
+                kind: ImportKind::Remote(url.to_string()),
+            });
+            i + url.len()
+        } else if at_token_start && is_local_import(rest) {
+            let (path, len) = parse_local_path(rest, dir)?;
+            imports.push(Import {
+                range: i..i + len,
+                kind: ImportKind::Local {
+                    path,
+                    relative: rest.starts_with('.'),
+                    code: !is_raw_import(&rest[len..]),
+                },
+            });
+            i + len
+        } else if is_label_char(bytes[i]) {
+            i + rest
+                .find(|c: char| c.is_ascii() && !is_label_char(c as u8))
+                .unwrap_or(rest.len())
+        } else {
+            i + rest.chars().next().map(char::len_utf8).unwrap_or(1)
+        };
+    }
+
+    Ok(i)
+}
+
+/// Scans the expressions interpolated into the (single or multi-line) text
+/// literal starting at `start`. Returns the end of the literal.
+fn scan_literal(
+    source: &str,
+    start: usize,
+    dir: &Path,
+    imports: &mut Vec<Import>,
+) -> Result<usize, String> {
+    let bytes = source.as_bytes();
+    let multiline = bytes[start..].starts_with(b"''");
+    let mut i = start + if multiline { 2 } else { 1 };
+
+    while i < bytes.len() {
+        let rest = &bytes[i..];
+        if rest.starts_with(b"${") {
+            // Continues after the closing `}`
+            i = scan_expression(source, i + 2, dir, true, imports)? + 1;
+        } else if multiline {
+            if rest.starts_with(b"'''") {
+                i += 3;
+            } else if rest.starts_with(b"''${") {
+                i += 4;
+            } else if rest.starts_with(b"''") {
+                i += 2;
+                break;
+            } else {
+                i += 1;
+            }
+        } else {
+            match bytes[i] {
+                b'\\' => i += 2,
+                b'"' => {
+                    i += 1;
+                    break;
+                }
+                _ => i += 1,
+            }
+        }
+    }
+
+    Ok(i.min(bytes.len()))
+}
+
+fn is_label_char(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, b'_' | b'-')
+}
+
+fn is_path_char(c: u8) -> bool {
+    c >= 0x80
+        || matches!(c, b'!' | b'$'..=b'\'' | b'*'..=b'+' | b'-'..=b'.' | b'0'..=b';' | b'=' | b'@'..=b'Z' | b'^'..=b'z' | b'|' | b'~')
+}
+
+fn block_comment_len(source: &str) -> usize {
+    let bytes = source.as_bytes();
+    let mut depth = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i..].starts_with(b"{-") {
+            depth += 1;
+            i += 2;
+        } else if bytes[i..].starts_with(b"-}") {
+            depth -= 1;
+            i += 2;
+            if depth == 0 {
+                return i;
+            }
+        } else {
+            i += 1;
+        }
+    }
+    source.len()
+}
+
+/// Name of the environment variable at the start of `source`, which directly
+/// follows an `env:`, and how long it is in `source`.
+fn env_var_name(source: &str) -> (&str, usize) {
+    match source.strip_prefix('"') {
+        Some(quoted) => match quoted.find('"') {
+            Some(len) => (&quoted[..len], len + 2),
+            None => (quoted, source.len()),
+        },
+        None => {
+            let len = source
+                .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+                .unwrap_or(source.len());
+            (&source[..len], len)
+        }
+    }
+}
+
+fn is_local_import(source: &str) -> bool {
+    let starts_path = |rest: &str| {
+        rest.bytes()
+            .next()
+            .map(|c| c == b'"' || (is_path_char(c) && c != b'/'))
+            .unwrap_or(false)
+    };
+    ["./", "../", "~/"].iter().any(|p| source.starts_with(p)) || {
+        source.starts_with('/') && starts_path(&source[1..])
+    }
+}
+
+/// Parses the local import path at the start of `source`. Returns the
+/// absolute path it refers to and the length of the import in `source`.
+fn parse_local_path(source: &str, dir: &Path) -> Result<(PathBuf, usize), String> {
+    let bytes = source.as_bytes();
+    let (mut path, mut i) = if source.starts_with("~/") {
+        let home = env::var_os("HOME").ok_or_else(|| "HOME is not set".to_string())?;
+        (PathBuf::from(home), 1)
+    } else if source.starts_with("../") {
+        (dir.join(".."), 2)
+    } else if source.starts_with("./") {
+        (dir.to_path_buf(), 1)
+    } else {
+        (PathBuf::from("/"), 0)
+    };
+
+    while i < bytes.len() && bytes[i] == b'/' {
+        i += 1;
+        if bytes.get(i) == Some(&b'"') {
+            let len = source[i + 1..]
+                .find('"')
+                .ok_or_else(|| "unterminated quoted path component".to_string())?;
+            path.push(&source[i + 1..i + 1 + len]);
+            i += len + 2;
+        } else {
+            let len = bytes[i..].iter().take_while(|c| is_path_char(**c)).count();
+            path.push(&source[i..i + len]);
+            i += len;
+        }
+    }
+
+    Ok((normalize(&path), i))
+}
+
+/// Whether the import whose path ends right before `source` is imported as
+/// something else than Dhall code.
+fn is_raw_import(source: &str) -> bool {
+    let mut rest = source.trim_start();
+    if rest.starts_with("sha256:") {
+        rest = rest
+            .trim_start_matches(|c: char| !c.is_whitespace())
+            .trim_start();
+    }
+    rest.starts_with("as")
+        && rest["as".len()..]
+            .trim_start()
+            .starts_with(|c: char| c.is_ascii_uppercase())
+}
+
+/// Resolves `.` and `..` in the absolute `path` lexically, like Dhall does
+/// for imports.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::from("/");
+    for component in path.components() {
+        match component {
+            Component::Normal(c) => normalized.push(c),
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            _ => (),
+        }
+    }
+    normalized
+}
+
+/// Formats the absolute `path` as a Dhall import path.
+fn dhall_path(path: &Path) -> String {
+    normalize(path)
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(c) => Some(c.to_string_lossy()),
+            _ => None,
+        })
+        .map(|c| {
+            if !c.is_empty() && c.bytes().all(is_path_char) {
+                format!("/{}", c)
+            } else {
+                format!("/\"{}\"", c)
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The text of each import found in `source`.
+    fn scanned(source: &str) -> Vec<&str> {
+        scan(source, Path::new("/config"))
+            .unwrap()
+            .into_iter()
+            .map(|import| &source[import.range])
+            .collect()
+    }
+
+    fn resolver(dir: &Path) -> Resolver {
+        Resolver {
+            types_path: PathBuf::from("/data/awc/Types.dhall"),
+            config_path: dir.join("config.dhall"),
+            staging_dir: dir.join("staging"),
+            staged: HashSet::new(),
+            visited: HashSet::new(),
+            forbid_remote_imports: true,
+            remote_imports: Vec::new(),
+            fingerprint: DefaultHasher::new(),
+            fingerprintable: true,
+        }
+    }
+
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("awc-imports-{}-{}", name, process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn finds_imports() {
+        let source = "let Types = env:AWC_TYPES in ./a.dhall ../b.dhall as Text";
+        assert_eq!(
+            scan(source, Path::new("/config/awc")).unwrap(),
+            vec![
+                Import {
+                    range: 12..25,
+                    kind: ImportKind::Env("AWC_TYPES".to_string()),
+                },
+                Import {
+                    range: 29..38,
+                    kind: ImportKind::Local {
+                        path: PathBuf::from("/config/awc/a.dhall"),
+                        relative: true,
+                        code: true,
+                    },
+                },
+                Import {
+                    range: 39..49,
+                    kind: ImportKind::Local {
+                        path: PathBuf::from("/config/b.dhall"),
+                        relative: true,
+                        code: false,
+                    },
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn skips_comments() {
+        let source = "-- ./a.dhall env:AWC_TYPES\n{- ./b.dhall {- ./c.dhall -} -} ./d.dhall";
+        assert_eq!(scanned(source), vec!["./d.dhall"]);
+    }
+
+    #[test]
+    fn skips_text_literals() {
+        assert_eq!(
+            scanned(r#""./a.dhall \" ./b.dhall" ./c.dhall"#),
+            vec!["./c.dhall"]
+        );
+        assert_eq!(
+            scanned("'' ./a.dhall ''' ./b.dhall '' ./c.dhall"),
+            vec!["./c.dhall"]
+        );
+    }
+
+    #[test]
+    fn finds_imports_in_interpolations() {
+        assert_eq!(
+            scanned(r#""./a.dhall ${./b.dhall} \${./c.dhall}""#),
+            vec!["./b.dhall"]
+        );
+        assert_eq!(
+            scanned("'' ${./a.dhall} ''${./b.dhall} ''"),
+            vec!["./a.dhall"]
+        );
+    }
+
+    #[test]
+    fn interpolations_end_at_the_matching_brace() {
+        assert_eq!(
+            scanned(r#""${{ x = "}" }.x} ./a.dhall" ./b.dhall"#),
+            vec!["./b.dhall"]
+        );
+        assert_eq!(
+            scanned(r#""${"${./a.dhall}"} ./b.dhall""#),
+            vec!["./a.dhall"]
+        );
+    }
+
+    #[test]
+    fn skips_backtick_labels() {
+        assert_eq!(
+            scanned("let `./a.dhall` = ./b.dhall in `./a.dhall`"),
+            vec!["./b.dhall"]
+        );
+    }
+
+    #[test]
+    fn finds_imports_after_the_merge_operator() {
+        assert_eq!(
+            scanned("defaults//./overrides.dhall"),
+            vec!["./overrides.dhall"]
+        );
+        assert_eq!(
+            scanned("defaults // /etc/awc.dhall"),
+            vec!["/etc/awc.dhall"]
+        );
+    }
+
+    #[test]
+    fn stages_relative_imports() {
+        let dir = test_dir("relative");
+        fs::create_dir_all(dir.join("sub")).unwrap();
+        fs::write(
+            dir.join("config.dhall"),
+            "let Types = env:AWC_TYPES in ./sub/a.dhall /etc/b.dhall",
+        )
+        .unwrap();
+        fs::write(dir.join("sub/a.dhall"), "../c.txt as Text").unwrap();
+        fs::write(dir.join("c.txt"), "c").unwrap();
+
+        let staged = resolver(&dir).stage().map(|path| {
+            let read = |p: &Path| fs::read_to_string(p).unwrap_or_default();
+            let mirrored = path.parent().unwrap().to_path_buf();
+            (
+                path.clone(),
+                read(&path),
+                read(&mirrored.join("sub/a.dhall")),
+                read(&mirrored.join("c.txt")),
+            )
+        });
+        let _ = fs::remove_dir_all(&dir);
+
+        let (path, config, a, c) = staged.unwrap();
+        assert_eq!(
+            path,
+            dir.join("staging")
+                .join(dir.strip_prefix("/").unwrap())
+                .join("config.dhall")
+        );
+        assert_eq!(
+            config,
+            "let Types = /data/awc/Types.dhall in ./sub/a.dhall /etc/b.dhall"
+        );
+        assert_eq!(a, "../c.txt as Text");
+        assert_eq!(c, "c");
+    }
+
+    #[test]
+    fn forbids_remote_imports() {
+        let dir = test_dir("remote");
+        fs::write(dir.join("config.dhall"), "/etc/awc/a.dhall ? ./a.dhall").unwrap();
+        fs::write(
+            dir.join("a.dhall"),
+            "https://example.com/a.dhall sha256:0000",
+        )
+        .unwrap();
+
+        let staged = resolver(&dir).stage();
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(
+            staged,
+            Err(format!(
+                "{}: remote import https://example.com/a.dhall is forbidden",
+                dir.join("a.dhall").display()
+            ))
+        );
+    }
+
+    #[test]
+    fn fingerprints_absolute_imports() {
+        let dir = test_dir("fingerprint");
+        fs::write(dir.join("b.dhall"), "1").unwrap();
+        fs::write(
+            dir.join("config.dhall"),
+            format!("{} + 1", dhall_path(&dir.join("b.dhall"))),
+        )
+        .unwrap();
+
+        let fingerprint = || {
+            let mut resolver = resolver(&dir);
+            resolver.stage().map(|_| resolver.fingerprint())
+        };
+        let fingerprints = fingerprint().and_then(|first| {
+            fs::write(dir.join("b.dhall"), "2").map_err(|e| e.to_string())?;
+            Ok((first, fingerprint()?))
+        });
+        let _ = fs::remove_dir_all(&dir);
+
+        let (first, second) = fingerprints.unwrap();
+        assert!(first.is_some());
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn removes_stale_staged_copies() {
+        let dir = test_dir("stale");
+        fs::write(dir.join("config.dhall"), "./a.dhall").unwrap();
+        fs::write(dir.join("a.dhall"), "{=}").unwrap();
+
+        let staged_a = resolver(&dir).stage().and_then(|path| {
+            let staged_a = path.with_file_name("a.dhall");
+            let before = staged_a.exists();
+            fs::write(dir.join("config.dhall"), "{=}").map_err(|e| e.to_string())?;
+            resolver(&dir).stage()?;
+            Ok((before, staged_a.exists()))
+        });
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(staged_a, Ok((true, false)));
+    }
+
+    #[test]
+    fn keeps_unchanged_staged_copies() {
+        use std::os::unix::fs::MetadataExt;
+
+        let dir = test_dir("unchanged");
+        fs::write(dir.join("config.dhall"), "{ a = 1 }").unwrap();
+
+        let inode = || fs::metadata(resolver(&dir).stage().unwrap()).unwrap().ino();
+        let first = inode();
+        let second = inode();
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(first, second);
+    }
+}
@@ -0,0 +1,55 @@
+use crate::{
+    glob_matches, AwcInputType, AwcKeyboardType, InputConfig, InputMatch, KeyboardConfig,
+    KeyboardMatch,
+};
+
+/// Merges all input configs that match the device. Configs that come later
+/// take precedence, so that general configs (e.g. for all touchpads) can be
+/// refined for specific devices.
+pub(crate) fn merge(
+    inputs: &[InputConfig],
+    name: &str,
+    type_: AwcInputType,
+) -> Option<InputConfig> {
+    inputs
+        .iter()
+        .filter(|input| input.matches.iter().all(|m| matches(m, name, type_)))
+        .fold(None, |merged: Option<InputConfig>, input| {
+            Some(match merged {
+                None => input.clone(),
+                Some(merged) => InputConfig {
+                    matches: Vec::new(),
+                    tap: input.tap.or(merged.tap),
+                    natural_scroll: input.natural_scroll.or(merged.natural_scroll),
+                    accel_profile: input.accel_profile.or(merged.accel_profile),
+                    accel_speed: input.accel_speed.or(merged.accel_speed),
+                    scroll_method: input.scroll_method.or(merged.scroll_method),
+                    disable_while_typing: input
+                        .disable_while_typing
+                        .or(merged.disable_while_typing),
+                    left_handed: input.left_handed.or(merged.left_handed),
+                },
+            })
+        })
+}
+
+fn matches(input_match: &InputMatch, name: &str, type_: AwcInputType) -> bool {
+    match input_match {
+        InputMatch::Name(pattern) => glob_matches(pattern, name),
+        InputMatch::Type(expected) => *expected == type_,
+    }
+}
+
+/// Returns the index of the first keyboard config that matches the keyboard.
+pub(crate) fn find_keyboard(
+    keyboards: &[KeyboardConfig],
+    name: &str,
+    type_: AwcKeyboardType,
+) -> Option<usize> {
+    keyboards.iter().position(|keyboard| {
+        keyboard.matches.iter().all(|m| match m {
+            KeyboardMatch::Name(pattern) => glob_matches(pattern, name),
+            KeyboardMatch::Type(expected) => *expected == type_,
+        })
+    })
+}
@@ -1,18 +1,31 @@
 extern crate xdg;
 
+mod cache;
+mod colors;
+mod diff;
+mod imports;
+mod inputs;
+mod outputs;
+mod validate;
+mod xkb;
+
 use libc::size_t;
-use serde::Deserialize;
-use std::env;
+use serde::{Deserialize, Serialize};
+use std::any::Any;
 use std::ffi::{CStr, CString};
-use std::os::raw::c_char;
+use std::os::raw::{c_char, c_int};
+use std::path::Path;
+use validate::Diagnostic;
 
 // Intermediate structures
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
 enum Action {
     Close,
     ConfigReload,
+    EnterMode(String),
     Execute(String),
+    ExitMode,
     Expand,
     FocusDown,
     FocusUp,
@@ -23,6 +36,7 @@ enum Action {
     MoveTo(String),
     MoveToOutput(u8),
     ResetLayouts,
+    Sequence(Vec<Action>),
     Shrink,
     Sink,
     SwapDown,
@@ -38,12 +52,14 @@ enum Action {
 }
 
 impl Action {
-    fn to_awc(&self) -> Result<AwcAction, String> {
+    fn to_awc(&self, arena: &mut Arena) -> Result<AwcAction, String> {
         let mut action = AwcAction {
             execute: std::ptr::null(),
             expand: false,
             close: false,
             config_reload: false,
+            enter_mode: std::ptr::null(),
+            exit_mode: false,
             focus_down: false,
             focus_up: false,
             focus_primary: false,
@@ -61,6 +77,8 @@ impl Action {
             reset_layouts: false,
             move_to: std::ptr::null(),
             move_to_output: 0,
+            sequence: std::ptr::null(),
+            number_of_sequence_actions: 0,
             switch_vt: 0,
             assign_scratchpad: false,
             toggle_scratchpad: false,
@@ -69,17 +87,30 @@ impl Action {
         match self {
             Action::Close => action.close = true,
             Action::ConfigReload => action.config_reload = true,
-            Action::Execute(cmd) => action.execute = str_to_c_char(cmd, "execute command")?,
+            Action::EnterMode(mode) => action.enter_mode = arena.str(mode, "mode name")?,
+            Action::Execute(cmd) => action.execute = arena.str(cmd, "execute command")?,
+            Action::ExitMode => action.exit_mode = true,
             Action::Expand => action.expand = true,
             Action::Focus(nth) => action.focus = *nth,
             Action::FocusDown => action.focus_down = true,
             Action::FocusUp => action.focus_up = true,
             Action::FocusOutput(output) => action.focus_output = *output,
             Action::FocusPrimary => action.focus_primary = true,
-            Action::GreedyView(ws) => action.greedy_view = str_to_c_char(ws, "greedyView target")?,
-            Action::MoveTo(ws) => action.move_to = str_to_c_char(ws, "move target")?,
+            Action::GreedyView(ws) => action.greedy_view = arena.str(ws, "greedyView target")?,
+            Action::MoveTo(ws) => action.move_to = arena.str(ws, "move target")?,
             Action::MoveToOutput(output) => action.move_to_output = *output,
             Action::ResetLayouts => action.reset_layouts = true,
+            Action::Sequence(actions) => {
+                let mut flattened = Vec::new();
+                flatten_actions(actions, &mut flattened);
+                let converted_actions = flattened
+                    .into_iter()
+                    .map(|a| a.to_awc(arena))
+                    .collect::<Result<Vec<AwcAction>, String>>()?;
+                let (sequence, number_of_sequence_actions) = arena.slice(converted_actions);
+                action.sequence = sequence;
+                action.number_of_sequence_actions = number_of_sequence_actions;
+            }
             Action::Shrink => action.shrink = true,
             Action::Sink => action.sink = true,
             Action::SwapDown => action.swap_down = true,
@@ -91,43 +122,93 @@ impl Action {
             Action::ToggleScratchpad => action.toggle_scratchpad = true,
             Action::SwapWorkspaces => action.swap_workspaces = true,
             Action::SwapWorkspaceTagWith(ws) => {
-                action.swap_workspace_tag_with = str_to_c_char(ws, "swap workspace tag")?
+                action.swap_workspace_tag_with = arena.str(ws, "swap workspace tag")?
             }
-            Action::View(ws) => action.view = str_to_c_char(ws, "view target")?,
+            Action::View(ws) => action.view = arena.str(ws, "view target")?,
         }
         Ok(action)
     }
 }
 
-#[derive(Debug, Deserialize)]
+/// Collects the actions of nested sequences in order.
+fn flatten_actions<'a>(actions: &'a [Action], result: &mut Vec<&'a Action>) {
+    for action in actions {
+        match action {
+            Action::Sequence(nested) => flatten_actions(nested, result),
+            _ => result.push(action),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+enum ButtonAction {
+    Move,
+    Resize,
+    ResizeByFrame,
+    Run(Action),
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
 struct ButtonBinding {
     mods: Vec<AwcModifier>,
     button: AwcButton,
-    action: AwcButtonAction,
+    action: ButtonAction,
     window: AwcWindowSelection,
 }
 
 impl ButtonBinding {
-    fn to_awc(&self) -> AwcButtonBinding {
-        let (mods, number_of_mods) = vec_into_raw(self.mods.clone());
-        AwcButtonBinding {
+    fn to_awc(&self, arena: &mut Arena) -> Result<AwcButtonBinding, String> {
+        let (mods, number_of_mods) = arena.slice(self.mods.clone());
+        let (action, run) = match &self.action {
+            ButtonAction::Move => (AwcButtonAction::Move, std::ptr::null()),
+            ButtonAction::Resize => (AwcButtonAction::Resize, std::ptr::null()),
+            ButtonAction::ResizeByFrame => (AwcButtonAction::ResizeByFrame, std::ptr::null()),
+            ButtonAction::Run(action) => {
+                let converted = action.to_awc(arena)?;
+                (AwcButtonAction::Run, arena.slice(vec![converted]).0)
+            }
+        };
+        Ok(AwcButtonBinding {
             mods,
             number_of_mods,
             button: self.button,
-            action: self.action,
+            action,
+            run,
             window: self.window,
-        }
+        })
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GestureBinding {
+    mods: Vec<AwcModifier>,
+    fingers: u8,
+    gesture: AwcGesture,
+    action: Action,
+}
+
+impl GestureBinding {
+    fn to_awc(&self, arena: &mut Arena) -> Result<AwcGestureBinding, String> {
+        let (mods, number_of_mods) = arena.slice(self.mods.clone());
+        Ok(AwcGestureBinding {
+            action: self.action.to_awc(arena)?,
+            mods,
+            number_of_mods,
+            fingers: self.fingers,
+            gesture: self.gesture,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
 enum Key {
     Code(u32),
     Sym(String),
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
 struct KeyBinding {
     mods: Vec<AwcModifier>,
@@ -136,14 +217,14 @@ struct KeyBinding {
 }
 
 impl KeyBinding {
-    fn to_awc(&self) -> Result<AwcKeyBinding, String> {
-        let (mods, number_of_mods) = vec_into_raw(self.mods.clone());
+    fn to_awc(&self, arena: &mut Arena) -> Result<AwcKeyBinding, String> {
+        let (mods, number_of_mods) = arena.slice(self.mods.clone());
         let (code, sym) = match &self.key {
             Key::Code(code) => (*code, std::ptr::null()),
-            Key::Sym(sym) => (0, str_to_c_char(sym, "Key symbol")?),
+            Key::Sym(sym) => (0, arena.str(sym, "Key symbol")?),
         };
         Ok(AwcKeyBinding {
-            action: self.action.to_awc()?,
+            action: self.action.to_awc(arena)?,
             mods,
             number_of_mods,
             code,
@@ -152,43 +233,539 @@ impl KeyBinding {
     }
 }
 
-#[derive(Debug, Deserialize)]
+/// An entry of `keyboards`, which only sets the layout of a type of
+/// keyboards.
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
-struct KeyboardConfig {
+struct SimpleKeyboardConfig {
     layout: String,
     type_: AwcKeyboardType,
 }
 
+impl SimpleKeyboardConfig {
+    /// The equivalent entry of `keyboardSettings`, with the defaults of
+    /// `Keyboard` in Types.dhall.
+    fn to_keyboard_config(&self) -> KeyboardConfig {
+        KeyboardConfig {
+            matches: vec![KeyboardMatch::Type(self.type_)],
+            layout: self.layout.clone(),
+            variant: String::new(),
+            model: String::new(),
+            options: "compose:rctrl".to_string(),
+            repeat_rate: 25,
+            repeat_delay: 600,
+            numlock: false,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct KeyboardConfig {
+    matches: Vec<KeyboardMatch>,
+    layout: String,
+    variant: String,
+    model: String,
+    options: String,
+    repeat_rate: u32,
+    repeat_delay: u32,
+    numlock: bool,
+}
+
 impl KeyboardConfig {
-    fn to_awc(&self) -> Result<AwcKeyboardConfig, String> {
+    fn to_awc(&self, arena: &mut Arena) -> Result<AwcKeyboardConfig, String> {
+        let converted_matches = self
+            .matches
+            .iter()
+            .map(|m| m.to_awc(arena))
+            .collect::<Result<Vec<AwcKeyboardMatch>, String>>()?;
+        let (matches, number_of_matches) = arena.slice(converted_matches);
         Ok(AwcKeyboardConfig {
-            layout: str_to_c_char(&self.layout, "keyboard layout")?,
-            type_: self.type_,
+            matches,
+            number_of_matches,
+            layout: arena.str(&self.layout, "keyboard layout")?,
+            variant: arena.str(&self.variant, "keyboard variant")?,
+            model: arena.str(&self.model, "keyboard model")?,
+            options: arena.str(&self.options, "keyboard options")?,
+            repeat_rate: self.repeat_rate as i32,
+            repeat_delay: self.repeat_delay as i32,
+            numlock: self.numlock,
         })
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+enum KeyboardMatch {
+    Name(String),
+    Type(AwcKeyboardType),
+}
+
+impl KeyboardMatch {
+    fn to_awc(&self, arena: &mut Arena) -> Result<AwcKeyboardMatch, String> {
+        Ok(match self {
+            KeyboardMatch::Name(pattern) => AwcKeyboardMatch {
+                name: arena.str(pattern, "keyboard name")?,
+                type_: AwcKeyboardType::Builtin,
+            },
+            KeyboardMatch::Type(type_) => AwcKeyboardMatch {
+                name: std::ptr::null(),
+                type_: *type_,
+            },
+        })
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
-struct OutputConfig {
+struct InputConfig {
+    matches: Vec<InputMatch>,
+    tap: Option<bool>,
+    natural_scroll: Option<bool>,
+    accel_profile: Option<AccelProfile>,
+    accel_speed: Option<f64>,
+    scroll_method: Option<ScrollMethod>,
+    disable_while_typing: Option<bool>,
+    left_handed: Option<bool>,
+}
+
+impl InputConfig {
+    fn to_awc(&self, arena: &mut Arena) -> Result<AwcInputConfig, String> {
+        let converted_matches = self
+            .matches
+            .iter()
+            .map(|m| m.to_awc(arena))
+            .collect::<Result<Vec<AwcInputMatch>, String>>()?;
+        let (matches, number_of_matches) = arena.slice(converted_matches);
+        Ok(AwcInputConfig {
+            matches,
+            number_of_matches,
+            ..self.settings_to_awc()
+        })
+    }
+
+    /// Converts everything but the matchers.
+    fn settings_to_awc(&self) -> AwcInputConfig {
+        let toggle = |value: Option<bool>| match value {
+            None => AwcToggle::Default,
+            Some(true) => AwcToggle::Enabled,
+            Some(false) => AwcToggle::Disabled,
+        };
+        AwcInputConfig {
+            matches: std::ptr::null(),
+            number_of_matches: 0,
+            tap: toggle(self.tap),
+            natural_scroll: toggle(self.natural_scroll),
+            accel_profile: match self.accel_profile {
+                None => AwcAccelProfile::Default,
+                Some(AccelProfile::Flat) => AwcAccelProfile::Flat,
+                Some(AccelProfile::Adaptive) => AwcAccelProfile::Adaptive,
+            },
+            has_accel_speed: self.accel_speed.is_some(),
+            accel_speed: self.accel_speed.unwrap_or(0.0),
+            scroll_method: match self.scroll_method {
+                None => AwcScrollMethod::Default,
+                Some(ScrollMethod::NoScroll) => AwcScrollMethod::NoScroll,
+                Some(ScrollMethod::TwoFinger) => AwcScrollMethod::TwoFinger,
+                Some(ScrollMethod::Edge) => AwcScrollMethod::Edge,
+                Some(ScrollMethod::OnButtonDown) => AwcScrollMethod::OnButtonDown,
+            },
+            disable_while_typing: toggle(self.disable_while_typing),
+            left_handed: toggle(self.left_handed),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+enum InputMatch {
+    Name(String),
+    Type(AwcInputType),
+}
+
+impl InputMatch {
+    fn to_awc(&self, arena: &mut Arena) -> Result<AwcInputMatch, String> {
+        Ok(match self {
+            InputMatch::Name(pattern) => AwcInputMatch {
+                name: arena.str(pattern, "input name")?,
+                type_: AwcInputType::Pointer,
+            },
+            InputMatch::Type(type_) => AwcInputMatch {
+                name: std::ptr::null(),
+                type_: *type_,
+            },
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+enum AccelProfile {
+    Flat,
+    Adaptive,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+enum ScrollMethod {
+    NoScroll,
+    TwoFinger,
+    Edge,
+    OnButtonDown,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Mode {
+    name: String,
+    key_bindings: Vec<KeyBinding>,
+}
+
+impl Mode {
+    fn to_awc(&self, arena: &mut Arena) -> Result<AwcMode, String> {
+        let converted_key_bindings = self
+            .key_bindings
+            .iter()
+            .map(|b| b.to_awc(arena))
+            .collect::<Result<Vec<AwcKeyBinding>, String>>()?;
+        let (key_bindings, number_of_key_bindings) = arena.slice(converted_key_bindings);
+        Ok(AwcMode {
+            name: arena.str(&self.name, "mode name")?,
+            key_bindings,
+            number_of_key_bindings,
+        })
+    }
+}
+
+/// An entry of `outputs`, which only sets the position and the scale.
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+struct SimpleOutputConfig {
     name: String,
     x: i32,
     y: i32,
     scale: f32,
 }
 
+impl SimpleOutputConfig {
+    /// The equivalent entry of `outputSettings`, which matches the output by
+    /// connector and has the defaults of `Output` in Types.dhall.
+    fn to_output_config(&self) -> OutputConfig {
+        OutputConfig {
+            matches: vec![OutputMatch::Connector(self.name.clone())],
+            x: self.x,
+            y: self.y,
+            scale: self.scale,
+            mode: None,
+            transform: AwcTransform::Normal,
+            enabled: true,
+            adaptive_sync: false,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct OutputConfig {
+    matches: Vec<OutputMatch>,
+    x: i32,
+    y: i32,
+    scale: f32,
+    mode: Option<OutputMode>,
+    transform: AwcTransform,
+    enabled: bool,
+    adaptive_sync: bool,
+}
+
 impl OutputConfig {
-    fn to_awc(&self) -> Result<AwcOutputConfig, String> {
-        Ok(AwcOutputConfig {
-            name: str_to_c_char(&self.name, "output name")?,
+    fn to_awc(&self) -> AwcOutputConfig {
+        AwcOutputConfig {
             x: self.x,
             y: self.y,
             scale: self.scale,
+            mode: self
+                .mode
+                .as_ref()
+                .map(OutputMode::to_awc)
+                .unwrap_or(AwcOutputMode {
+                    width: 0,
+                    height: 0,
+                    refresh: 0,
+                }),
+            transform: self.transform,
+            enabled: self.enabled,
+            adaptive_sync: self.adaptive_sync,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+struct Profile {
+    name: String,
+    outputs: Vec<OutputConfig>,
+}
+
+impl Profile {
+    fn to_awc(&self, arena: &mut Arena) -> Result<AwcProfile, String> {
+        let converted_outputs = self
+            .outputs
+            .iter()
+            .map(OutputConfig::to_awc)
+            .collect::<Vec<AwcOutputConfig>>();
+        let (outputs, number_of_outputs) = arena.slice(converted_outputs);
+        Ok(AwcProfile {
+            name: arena.str(&self.name, "profile name")?,
+            outputs,
+            number_of_outputs,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+enum OutputMatch {
+    Connector(String),
+    Make(String),
+    Model(String),
+    Serial(String),
+    Description(String),
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+struct OutputMode {
+    width: u32,
+    height: u32,
+    /// In Hz
+    refresh: Option<f64>,
+}
+
+impl OutputMode {
+    fn to_awc(&self) -> AwcOutputMode {
+        AwcOutputMode {
+            width: self.width as i32,
+            height: self.height as i32,
+            refresh: self
+                .refresh
+                .map(|hz| (hz * 1000.0).round() as i32)
+                .unwrap_or(0),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+enum Pattern {
+    Exact(String),
+    Glob(String),
+    Regex(String),
+}
+
+impl Pattern {
+    fn to_awc(
+        &self,
+        arena: &mut Arena,
+        property: AwcWindowProperty,
+    ) -> Result<AwcWindowMatcher, String> {
+        let (kind, pattern) = match self {
+            Pattern::Exact(pattern) => (AwcPatternKind::Exact, pattern),
+            Pattern::Glob(pattern) => (AwcPatternKind::Glob, pattern),
+            Pattern::Regex(pattern) => (AwcPatternKind::Regex, pattern),
+        };
+        Ok(AwcWindowMatcher {
+            property,
+            kind,
+            pattern: arena.str(pattern, "window rule pattern")?,
         })
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+enum WindowMatch {
+    AppId(Pattern),
+    Title(Pattern),
+    Class(Pattern),
+}
+
+impl WindowMatch {
+    fn to_awc(&self, arena: &mut Arena) -> Result<AwcWindowMatcher, String> {
+        match self {
+            WindowMatch::AppId(pattern) => pattern.to_awc(arena, AwcWindowProperty::AppId),
+            WindowMatch::Title(pattern) => pattern.to_awc(arena, AwcWindowProperty::Title),
+            WindowMatch::Class(pattern) => pattern.to_awc(arena, AwcWindowProperty::Class),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+enum WindowEffect {
+    Workspace(String),
+    Output(u8),
+    Float,
+    Sink,
+    Fullscreen,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WindowRule {
+    matches: Vec<WindowMatch>,
+    effects: Vec<WindowEffect>,
+}
+
+impl WindowRule {
+    fn to_awc(&self, arena: &mut Arena) -> Result<AwcWindowRule, String> {
+        // Later effects override earlier ones
+        let mut workspace = None;
+        let mut output = 0;
+        let mut float = false;
+        let mut sink = false;
+        let mut fullscreen = false;
+        for effect in &self.effects {
+            match effect {
+                WindowEffect::Workspace(ws) => workspace = Some(ws),
+                WindowEffect::Output(n) => output = *n,
+                WindowEffect::Float => {
+                    float = true;
+                    sink = false;
+                }
+                WindowEffect::Sink => {
+                    sink = true;
+                    float = false;
+                }
+                WindowEffect::Fullscreen => fullscreen = true,
+            }
+        }
+
+        let converted_matchers = self
+            .matches
+            .iter()
+            .map(|m| m.to_awc(arena))
+            .collect::<Result<Vec<AwcWindowMatcher>, String>>()?;
+        let (matchers, number_of_matchers) = arena.slice(converted_matchers);
+        Ok(AwcWindowRule {
+            matchers,
+            number_of_matchers,
+            workspace: match workspace {
+                Some(ws) => arena.str(ws, "window rule workspace")?,
+                None => std::ptr::null(),
+            },
+            output,
+            float,
+            sink,
+            fullscreen,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+enum LayoutOp {
+    Choose,
+    Full,
+    Capped(isize),
+    Magnify(f64),
+    Reflected(AwcDirection),
+    Rotated,
+    Push,
+    Tiled {
+        split: f64,
+        delta: f64,
+    },
+    Spacing {
+        inner: u32,
+        outer: u32,
+        #[serde(rename = "smartGaps")]
+        smart_gaps: bool,
+    },
+    Grid(AwcGrid),
+    ThreeColumn {
+        split: f64,
+        delta: f64,
+        centered: bool,
+    },
+    Spiral {
+        ratio: f64,
+    },
+    Tabbed,
+    Named(String),
+}
+
+impl LayoutOp {
+    fn to_awc(&self, arena: &mut Arena) -> Result<AwcLayoutOp, String> {
+        Ok(match self {
+            LayoutOp::Choose => AwcLayoutOp::Choose,
+            LayoutOp::Full => AwcLayoutOp::Full,
+            LayoutOp::Capped(limit) => AwcLayoutOp::Capped(*limit),
+            LayoutOp::Magnify(magnification) => AwcLayoutOp::Magnify(*magnification),
+            LayoutOp::Reflected(direction) => AwcLayoutOp::Reflected(*direction),
+            LayoutOp::Rotated => AwcLayoutOp::Rotated,
+            LayoutOp::Push => AwcLayoutOp::Push,
+            LayoutOp::Tiled { split, delta } => AwcLayoutOp::Tiled {
+                split: *split,
+                delta: *delta,
+            },
+            LayoutOp::Spacing {
+                inner,
+                outer,
+                smart_gaps,
+            } => AwcLayoutOp::Spacing {
+                inner: *inner,
+                outer: *outer,
+                smart_gaps: *smart_gaps,
+            },
+            LayoutOp::Grid(grid) => AwcLayoutOp::Grid(*grid),
+            LayoutOp::ThreeColumn {
+                split,
+                delta,
+                centered,
+            } => AwcLayoutOp::ThreeColumn {
+                split: *split,
+                delta: *delta,
+                centered: *centered,
+            },
+            LayoutOp::Spiral { ratio } => AwcLayoutOp::Spiral { ratio: *ratio },
+            LayoutOp::Tabbed => AwcLayoutOp::Tabbed,
+            LayoutOp::Named(name) => AwcLayoutOp::Named(arena.str(name, "layout name")?),
+        })
+    }
+}
+
+fn layout_to_awc(
+    layout: &[LayoutOp],
+    arena: &mut Arena,
+) -> Result<(*const AwcLayoutOp, usize), String> {
+    let converted = layout
+        .iter()
+        .map(|op| op.to_awc(arena))
+        .collect::<Result<Vec<AwcLayoutOp>, String>>()?;
+    Ok(arena.slice(converted))
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Workspace {
+    tag: String,
+    name: Option<String>,
+    layout: Option<Vec<LayoutOp>>,
+    output: Option<u8>,
+}
+
+impl Workspace {
+    /// Converts the workspace with `tag`, which uses the defaults if it has no
+    /// `settings`.
+    fn to_awc(
+        tag: &str,
+        settings: Option<&Workspace>,
+        arena: &mut Arena,
+    ) -> Result<AwcWorkspace, String> {
+        let (layout, number_of_layout_ops) = match settings.and_then(|s| s.layout.as_ref()) {
+            Some(layout) => layout_to_awc(layout, arena)?,
+            None => (std::ptr::null(), 0),
+        };
+        let name = settings.and_then(|s| s.name.as_deref()).unwrap_or(tag);
+        Ok(AwcWorkspace {
+            tag: arena.str(tag, "workspace tag")?,
+            name: arena.str(name, "workspace name")?,
+            layout,
+            number_of_layout_ops,
+            output: settings.and_then(|s| s.output).unwrap_or(0),
+        })
+    }
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
 struct Config {
     border_width: u32,
@@ -196,72 +773,159 @@ struct Config {
     modifier: AwcModifier,
     display_error_cmd: String,
     button_bindings: Vec<ButtonBinding>,
+    gesture_bindings: Vec<GestureBinding>,
     key_bindings: Vec<KeyBinding>,
-    keyboards: Vec<KeyboardConfig>,
-    layout: Vec<AwcLayoutOp>,
-    outputs: Vec<OutputConfig>,
+    keyboards: Vec<SimpleKeyboardConfig>,
+    keyboard_settings: Vec<KeyboardConfig>,
+    inputs: Vec<InputConfig>,
+    layout: Vec<LayoutOp>,
+    modes: Vec<Mode>,
+    outputs: Vec<SimpleOutputConfig>,
+    output_settings: Vec<OutputConfig>,
+    profiles: Vec<Profile>,
     colors: AwcColorsConfig,
+    tabs: AwcTabsConfig,
+    window_rules: Vec<WindowRule>,
     workspaces: Vec<String>,
+    workspace_settings: Vec<Workspace>,
 }
 
 impl Config {
-    unsafe fn copy_to(self, target: *mut AwcConfig) -> Result<(), String> {
+    /// The entries of `keyboardSettings` followed by those of `keyboards`, so
+    /// that the former take precedence.
+    fn keyboard_configs(&self) -> Vec<KeyboardConfig> {
+        let simple = self
+            .keyboards
+            .iter()
+            .map(SimpleKeyboardConfig::to_keyboard_config);
+        self.keyboard_settings
+            .iter()
+            .cloned()
+            .chain(simple)
+            .collect()
+    }
+
+    /// The entries of `outputSettings` followed by those of `outputs`, which
+    /// therefore lose ties against the former.
+    fn output_configs(&self) -> Vec<OutputConfig> {
+        let simple = self
+            .outputs
+            .iter()
+            .map(SimpleOutputConfig::to_output_config);
+        self.output_settings.iter().cloned().chain(simple).collect()
+    }
+
+    /// The settings of the workspace with `tag`, if there are any in
+    /// `workspaceSettings`.
+    fn workspace_settings(&self, tag: &str) -> Option<&Workspace> {
+        self.workspace_settings.iter().find(|ws| ws.tag == tag)
+    }
+
+    fn to_awc(&self, arena: &mut Arena) -> Result<AwcConfig, String> {
         let converted_button_bindings = self
             .button_bindings
             .iter()
-            .map(|b| b.to_awc())
-            .collect::<Vec<AwcButtonBinding>>();
-        let (button_bindings, number_of_button_bindings) = vec_into_raw(converted_button_bindings);
-        (*target).button_bindings = button_bindings;
-        (*target).number_of_button_bindings = number_of_button_bindings;
+            .map(|b| b.to_awc(arena))
+            .collect::<Result<Vec<AwcButtonBinding>, String>>()?;
+        let (button_bindings, number_of_button_bindings) = arena.slice(converted_button_bindings);
+
+        let converted_gesture_bindings = self
+            .gesture_bindings
+            .iter()
+            .map(|b| b.to_awc(arena))
+            .collect::<Result<Vec<AwcGestureBinding>, String>>()?;
+        let (gesture_bindings, number_of_gesture_bindings) =
+            arena.slice(converted_gesture_bindings);
 
         let converted_key_bindings = self
             .key_bindings
             .iter()
-            .map(|b| b.to_awc())
+            .map(|b| b.to_awc(arena))
             .collect::<Result<Vec<AwcKeyBinding>, String>>()?;
-        let (key_bindings, number_of_key_bindings) = vec_into_raw(converted_key_bindings);
-        (*target).key_bindings = key_bindings;
-        (*target).number_of_key_bindings = number_of_key_bindings;
+        let (key_bindings, number_of_key_bindings) = arena.slice(converted_key_bindings);
 
         let converted_keyboards = self
-            .keyboards
+            .keyboard_configs()
             .iter()
-            .map(|k| k.to_awc())
+            .map(|k| k.to_awc(arena))
             .collect::<Result<Vec<AwcKeyboardConfig>, String>>()?;
-        let (keyboards, number_of_keyboards) = vec_into_raw(converted_keyboards);
-        (*target).keyboards = keyboards;
-        (*target).number_of_keyboards = number_of_keyboards;
+        let (keyboards, number_of_keyboards) = arena.slice(converted_keyboards);
 
-        let (layout, number_of_layout_ops) = vec_into_raw(self.layout);
-        (*target).layout = layout;
-        (*target).number_of_layout_ops = number_of_layout_ops;
+        let converted_inputs = self
+            .inputs
+            .iter()
+            .map(|i| i.to_awc(arena))
+            .collect::<Result<Vec<AwcInputConfig>, String>>()?;
+        let (inputs, number_of_inputs) = arena.slice(converted_inputs);
+
+        let (layout, number_of_layout_ops) = layout_to_awc(&self.layout, arena)?;
+
+        let converted_modes = self
+            .modes
+            .iter()
+            .map(|m| m.to_awc(arena))
+            .collect::<Result<Vec<AwcMode>, String>>()?;
+        let (modes, number_of_modes) = arena.slice(converted_modes);
 
         let converted_outputs = self
-            .outputs
+            .output_configs()
+            .iter()
+            .map(OutputConfig::to_awc)
+            .collect::<Vec<AwcOutputConfig>>();
+        let (outputs, number_of_outputs) = arena.slice(converted_outputs);
+
+        let converted_profiles = self
+            .profiles
+            .iter()
+            .map(|p| p.to_awc(arena))
+            .collect::<Result<Vec<AwcProfile>, String>>()?;
+        let (profiles, number_of_profiles) = arena.slice(converted_profiles);
+
+        let converted_window_rules = self
+            .window_rules
             .iter()
-            .map(|o| o.to_awc())
-            .collect::<Result<Vec<AwcOutputConfig>, String>>()?;
-        let (outputs, number_of_outputs) = vec_into_raw(converted_outputs);
-        (*target).outputs = outputs;
-        (*target).number_of_outputs = number_of_outputs;
+            .map(|r| r.to_awc(arena))
+            .collect::<Result<Vec<AwcWindowRule>, String>>()?;
+        let (window_rules, number_of_window_rules) = arena.slice(converted_window_rules);
 
         let converted_workspaces = self
             .workspaces
             .iter()
-            .map(|w| str_to_c_char(w, "workspace"))
-            .collect::<Result<Vec<*const c_char>, String>>()?;
-        let (workspaces, number_of_workspaces) = vec_into_raw(converted_workspaces);
-        (*target).workspaces = workspaces;
-        (*target).number_of_workspaces = number_of_workspaces;
+            .map(|tag| Workspace::to_awc(tag, self.workspace_settings(tag), arena))
+            .collect::<Result<Vec<AwcWorkspace>, String>>()?;
+        let (workspaces, number_of_workspaces) = arena.slice(converted_workspaces);
 
-        (*target).display_error_cmd = str_to_c_char(&self.display_error_cmd, "displayErrorCmd")?;
-        (*target).font = str_to_c_char(&self.font, "font")?;
-        (*target).modifier = self.modifier;
-        (*target).border_width = self.border_width;
-        (*target).colors = self.colors;
-
-        Ok(())
+        Ok(AwcConfig {
+            border_width: self.border_width,
+            display_error_cmd: arena.str(&self.display_error_cmd, "displayErrorCmd")?,
+            font: arena.str(&self.font, "font")?,
+            modifier: self.modifier,
+            colors: self.colors,
+            tabs: self.tabs,
+            button_bindings,
+            number_of_button_bindings,
+            gesture_bindings,
+            number_of_gesture_bindings,
+            key_bindings,
+            number_of_key_bindings,
+            keyboards,
+            number_of_keyboards,
+            inputs,
+            number_of_inputs,
+            layout,
+            number_of_layout_ops,
+            modes,
+            number_of_modes,
+            outputs,
+            number_of_outputs,
+            profiles,
+            number_of_profiles,
+            window_rules,
+            number_of_window_rules,
+            workspaces,
+            number_of_workspaces,
+            owner: std::ptr::null_mut(),
+        })
     }
 }
 
@@ -273,6 +937,8 @@ pub struct AwcAction {
     expand: bool,
     close: bool,
     config_reload: bool,
+    enter_mode: *const c_char,
+    exit_mode: bool,
     focus: u8,
     focus_down: bool,
     focus_up: bool,
@@ -290,13 +956,18 @@ pub struct AwcAction {
     reset_layouts: bool,
     move_to: *const c_char,
     move_to_output: u8,
+    /// Actions to execute in order, never contains sequences itself
+    sequence: *const AwcAction,
+    number_of_sequence_actions: size_t,
     assign_scratchpad: bool,
     toggle_scratchpad: bool,
     switch_vt: u8,
     view: *const c_char,
 }
 
-#[derive(Clone, Copy, Debug, Deserialize)]
+/// Deserializes from an RGBA record, a `#rrggbb[aa]` string or a color name,
+/// see `colors`.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
 #[repr(C)]
 pub struct AwcColor {
     r: u8,
@@ -305,7 +976,7 @@ pub struct AwcColor {
     a: u8,
 }
 
-#[derive(Clone, Copy, Debug, Deserialize)]
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
 #[repr(C)]
 pub enum AwcModifier {
     Alt,
@@ -315,19 +986,40 @@ pub enum AwcModifier {
     Shift,
 }
 
-#[derive(Clone, Copy, Debug, Deserialize)]
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Hash, Serialize)]
 #[repr(C)]
 pub enum AwcButton {
     Left,
     Right,
+    Middle,
+    Side,
+    Extra,
+    ScrollUp,
+    ScrollDown,
+    ScrollLeft,
+    ScrollRight,
 }
 
-#[derive(Clone, Copy, Debug, Deserialize)]
+impl AwcButton {
+    /// Whether the button is a scroll direction, i.e. can't be held down.
+    fn is_scroll(self) -> bool {
+        matches!(
+            self,
+            AwcButton::ScrollUp
+                | AwcButton::ScrollDown
+                | AwcButton::ScrollLeft
+                | AwcButton::ScrollRight
+        )
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
 #[repr(C)]
 pub enum AwcButtonAction {
     Move,
     Resize,
     ResizeByFrame,
+    Run,
 }
 
 #[repr(C)]
@@ -336,9 +1028,41 @@ pub struct AwcButtonBinding {
     number_of_mods: size_t,
     button: AwcButton,
     action: AwcButtonAction,
+    /// The action to run if `action` is `Run`, NULL otherwise
+    run: *const AwcAction,
     window: AwcWindowSelection,
 }
 
+/// A touchpad gesture, triggered when the fingers are lifted.
+/// cbindgen:prefix-with-name
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Hash, Serialize)]
+#[repr(C)]
+pub enum AwcGesture {
+    SwipeUp,
+    SwipeDown,
+    SwipeLeft,
+    SwipeRight,
+    /// Fingers move towards each other
+    PinchIn,
+    /// Fingers move away from each other
+    PinchOut,
+}
+
+impl AwcGesture {
+    fn is_swipe(self) -> bool {
+        !matches!(self, AwcGesture::PinchIn | AwcGesture::PinchOut)
+    }
+}
+
+#[repr(C)]
+pub struct AwcGestureBinding {
+    action: AwcAction,
+    mods: *const AwcModifier,
+    number_of_mods: size_t,
+    fingers: u8,
+    gesture: AwcGesture,
+}
+
 #[repr(C)]
 pub struct AwcKeyBinding {
     action: AwcAction,
@@ -348,28 +1072,197 @@ pub struct AwcKeyBinding {
     sym: *const c_char,
 }
 
+#[repr(C)]
+pub struct AwcWorkspace {
+    tag: *const c_char,
+    /// The name to display, the tag if no name is configured
+    name: *const c_char,
+    /// NULL if the workspace uses the config's layout
+    layout: *const AwcLayoutOp,
+    number_of_layout_ops: size_t,
+    /// The output the workspace is initially shown on (1-indexed), 0 if any
+    output: u8,
+}
+
+/// A named set of key bindings that replaces the regular key bindings while
+/// the mode is active.
+#[repr(C)]
+pub struct AwcMode {
+    name: *const c_char,
+    key_bindings: *const AwcKeyBinding,
+    number_of_key_bindings: size_t,
+}
+
+/// XKB settings of a keyboard. Empty strings select XKB's defaults.
 #[repr(C)]
 pub struct AwcKeyboardConfig {
+    /// The config applies to keyboards matched by all matchers
+    matches: *const AwcKeyboardMatch,
+    number_of_matches: size_t,
     layout: *const c_char,
+    variant: *const c_char,
+    model: *const c_char,
+    options: *const c_char,
+    /// In keys per second, 0 disables repeat
+    repeat_rate: i32,
+    /// In milliseconds
+    repeat_delay: i32,
+    numlock: bool,
+}
+
+/// Matches keyboards by name (a glob) or, if `name` is NULL, by type.
+#[repr(C)]
+pub struct AwcKeyboardMatch {
+    name: *const c_char,
     type_: AwcKeyboardType,
 }
 
-#[derive(Clone, Copy, Debug, Deserialize)]
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
 #[repr(C)]
 pub enum AwcKeyboardType {
     Builtin,
     External,
 }
 
+/// libinput settings for pointers and touchpads. Settings that are `Default`
+/// (or `accel_speed` if `has_accel_speed` is false) are left as they are.
 #[repr(C)]
-pub struct AwcOutputConfig {
+pub struct AwcInputConfig {
+    /// The config applies to devices matched by all matchers
+    matches: *const AwcInputMatch,
+    number_of_matches: size_t,
+    tap: AwcToggle,
+    natural_scroll: AwcToggle,
+    accel_profile: AwcAccelProfile,
+    has_accel_speed: bool,
+    accel_speed: f64,
+    scroll_method: AwcScrollMethod,
+    disable_while_typing: AwcToggle,
+    left_handed: AwcToggle,
+}
+
+/// Matches input devices by name (a glob) or, if `name` is NULL, by type.
+#[repr(C)]
+pub struct AwcInputMatch {
     name: *const c_char,
+    type_: AwcInputType,
+}
+
+/// cbindgen:prefix-with-name
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[repr(C)]
+pub enum AwcInputType {
+    Pointer,
+    Touchpad,
+}
+
+/// cbindgen:prefix-with-name
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+pub enum AwcToggle {
+    Default,
+    Enabled,
+    Disabled,
+}
+
+/// cbindgen:prefix-with-name
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+pub enum AwcAccelProfile {
+    Default,
+    Flat,
+    Adaptive,
+}
+
+/// cbindgen:prefix-with-name
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+pub enum AwcScrollMethod {
+    Default,
+    NoScroll,
+    TwoFinger,
+    Edge,
+    OnButtonDown,
+}
+
+#[repr(C)]
+pub struct AwcOutputConfig {
     x: i32,
     y: i32,
     scale: f32,
+    mode: AwcOutputMode,
+    transform: AwcTransform,
+    enabled: bool,
+    adaptive_sync: bool,
+}
+
+/// Output configs that are used together if the connected outputs match
+/// them, see `awc_config_handle_find_profile`.
+#[repr(C)]
+pub struct AwcProfile {
+    name: *const c_char,
+    outputs: *const AwcOutputConfig,
+    number_of_outputs: size_t,
+}
+
+/// Describes a connected output, see `awc_config_handle_find_output`.
+#[repr(C)]
+pub struct AwcOutputInfo {
+    name: *const c_char,
+    make: *const c_char,
+    model: *const c_char,
+    serial: *const c_char,
+    description: *const c_char,
+}
+
+impl AwcOutputInfo {
+    /// # Safety
+    ///
+    /// All non-NULL strings must be NULL-terminated.
+    unsafe fn to_info(&self) -> outputs::OutputInfo {
+        let str_or_empty = |s: *const c_char| {
+            if s.is_null() {
+                ""
+            } else {
+                CStr::from_ptr(s).to_str().unwrap_or("")
+            }
+        };
+        outputs::OutputInfo {
+            name: str_or_empty(self.name),
+            make: str_or_empty(self.make),
+            model: str_or_empty(self.model),
+            serial: str_or_empty(self.serial),
+            description: str_or_empty(self.description),
+        }
+    }
+}
+
+/// The mode an output should use. `width` and `height` are 0 if the output
+/// should use its preferred mode, `refresh` (in mHz) is 0 if any refresh rate
+/// is fine.
+#[repr(C)]
+pub struct AwcOutputMode {
+    width: i32,
+    height: i32,
+    refresh: i32,
+}
+
+/// In the same order as `wl_output_transform`.
+/// cbindgen:prefix-with-name
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[repr(C)]
+pub enum AwcTransform {
+    Normal,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    Flipped,
+    Flipped90,
+    Flipped180,
+    Flipped270,
 }
 
-#[derive(Clone, Copy, Debug, Deserialize)]
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
 #[repr(C)]
 pub struct AwcColorsConfig {
@@ -378,7 +1271,7 @@ pub struct AwcColorsConfig {
     resize_frame: AwcColor,
 }
 
-#[derive(Clone, Copy, Debug, Deserialize)]
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
 #[repr(C)]
 pub struct AwcBorderColors {
@@ -386,7 +1279,7 @@ pub struct AwcBorderColors {
     inactive: AwcColor,
 }
 
-#[derive(Clone, Copy, Debug, Deserialize)]
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
 #[repr(C)]
 pub struct AwcOutputHudColors {
@@ -397,7 +1290,20 @@ pub struct AwcOutputHudColors {
     inactive_foreground: AwcColor,
 }
 
-#[derive(Clone, Copy, Debug, Deserialize)]
+/// The tab strip of the tabbed layout.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[repr(C)]
+pub struct AwcTabsConfig {
+    active_background: AwcColor,
+    active_foreground: AwcColor,
+    inactive_background: AwcColor,
+    inactive_foreground: AwcColor,
+    /// Height of the tab strip in pixels
+    height: u32,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
 #[repr(C)]
 pub enum AwcDirection {
     Horizontal,
@@ -405,7 +1311,7 @@ pub enum AwcDirection {
 }
 
 /// cbindgen:prefix-with-name
-#[derive(Clone, Copy, Debug, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 #[repr(C)]
 pub enum AwcLayoutOp {
     Choose,
@@ -415,16 +1321,120 @@ pub enum AwcLayoutOp {
     Reflected(AwcDirection),
     Rotated,
     Push,
-    Tiled { split: f64, delta: f64 },
+    Tiled {
+        split: f64,
+        delta: f64,
+    },
+    /// Puts `inner` pixels between views and `outer` pixels around them
+    Spacing {
+        inner: u32,
+        outer: u32,
+        /// No gaps if only one view is visible
+        smart_gaps: bool,
+    },
+    Grid(AwcGrid),
+    /// The main view takes `split` of the width, the other views are stacked
+    /// in two columns next to it or, if `centered`, on both sides of it
+    ThreeColumn {
+        split: f64,
+        delta: f64,
+        centered: bool,
+    },
+    /// Each view takes `ratio` of the area left by the views before it, going
+    /// around in a spiral
+    Spiral {
+        ratio: f64,
+    },
+    /// Like `Full`, but with a strip of tabs showing the titles of all views
+    Tabbed,
+    /// Replaces the description of the wrapped layout
+    Named(*const c_char),
 }
 
-#[derive(Clone, Copy, Debug, Deserialize)]
+/// How a grid layout chooses its number of columns.
+/// cbindgen:prefix-with-name
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[repr(C)]
+pub enum AwcGrid {
+    /// As many columns as rows
+    Auto,
+    /// A fixed number of columns
+    Columns(u32),
+    /// As many columns as needed to make views approximately this wide
+    /// relative to their height
+    AspectRatio(f64),
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
 #[repr(C)]
 pub enum AwcWindowSelection {
     Focused,
     UnderCursor,
 }
 
+/// cbindgen:prefix-with-name
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+pub enum AwcWindowProperty {
+    AppId,
+    Title,
+    Class,
+}
+
+/// cbindgen:prefix-with-name
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+pub enum AwcPatternKind {
+    Exact,
+    Glob,
+    Regex,
+}
+
+#[repr(C)]
+pub struct AwcWindowMatcher {
+    property: AwcWindowProperty,
+    kind: AwcPatternKind,
+    pattern: *const c_char,
+}
+
+/// A window rule applies its effects to a newly mapped view if all of its
+/// matchers match. `workspace` is NULL and `output` is 0 (outputs are
+/// 1-indexed) if the rule doesn't move the view.
+#[repr(C)]
+pub struct AwcWindowRule {
+    matchers: *const AwcWindowMatcher,
+    number_of_matchers: size_t,
+    workspace: *const c_char,
+    output: u8,
+    float: bool,
+    sink: bool,
+    fullscreen: bool,
+}
+
+/// cbindgen:prefix-with-name
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+pub enum AwcSeverity {
+    Warning,
+    Error,
+}
+
+/// A problem found while loading a config. `path` points to the offending
+/// setting (e.g. `keyBindings[3].action`) and is empty if the problem isn't
+/// specific to one setting.
+#[repr(C)]
+pub struct AwcDiagnostic {
+    severity: AwcSeverity,
+    message: *const c_char,
+    path: *const c_char,
+}
+
+#[repr(C)]
+pub struct AwcDiagnostics {
+    diagnostics: *const AwcDiagnostic,
+    number_of_diagnostics: size_t,
+}
+
 #[repr(C)]
 pub struct AwcConfig {
     border_width: u32,
@@ -432,32 +1442,151 @@ pub struct AwcConfig {
     font: *const c_char,
     modifier: AwcModifier,
     colors: AwcColorsConfig,
+    tabs: AwcTabsConfig,
 
     button_bindings: *const AwcButtonBinding,
     number_of_button_bindings: size_t,
 
+    gesture_bindings: *const AwcGestureBinding,
+    number_of_gesture_bindings: size_t,
+
     key_bindings: *const AwcKeyBinding,
     number_of_key_bindings: size_t,
 
     keyboards: *const AwcKeyboardConfig,
     number_of_keyboards: size_t,
 
+    inputs: *const AwcInputConfig,
+    number_of_inputs: size_t,
+
     layout: *const AwcLayoutOp,
     number_of_layout_ops: size_t,
 
+    modes: *const AwcMode,
+    number_of_modes: size_t,
+
     outputs: *const AwcOutputConfig,
     number_of_outputs: size_t,
 
-    workspaces: *const *const c_char,
+    profiles: *const AwcProfile,
+    number_of_profiles: size_t,
+
+    window_rules: *const AwcWindowRule,
+    number_of_window_rules: size_t,
+
+    workspaces: *const AwcWorkspace,
     number_of_workspaces: size_t,
+
+    /// The handle that owns everything referenced by this structure. Only
+    /// set for structures populated by `awc_config_load`.
+    owner: *mut AwcConfigHandle,
+}
+
+/// Options for loading a config.
+#[repr(C)]
+#[derive(Default)]
+pub struct AwcLoadOptions {
+    /// Fail instead of fetching imports from the network.
+    forbid_remote_imports: bool,
+}
+
+/// Owns a loaded config and all memory referenced by it.
+pub struct AwcConfigHandle {
+    config: AwcConfig,
+    source: Config,
+    // Only kept to keep the config's pointers valid
+    _arena: Arena,
+}
+
+#[repr(C)]
+pub struct AwcWorkspaceRename {
+    old: size_t,
+    new: size_t,
+}
+
+/// What changed between two configs. Indices of removed elements refer to
+/// the old config, all other indices to the new config.
+#[repr(C)]
+pub struct AwcConfigDiff {
+    added_button_bindings: *const size_t,
+    number_of_added_button_bindings: size_t,
+    removed_button_bindings: *const size_t,
+    number_of_removed_button_bindings: size_t,
+    added_gesture_bindings: *const size_t,
+    number_of_added_gesture_bindings: size_t,
+    removed_gesture_bindings: *const size_t,
+    number_of_removed_gesture_bindings: size_t,
+    added_key_bindings: *const size_t,
+    number_of_added_key_bindings: size_t,
+    removed_key_bindings: *const size_t,
+    number_of_removed_key_bindings: size_t,
+    added_outputs: *const size_t,
+    number_of_added_outputs: size_t,
+    removed_outputs: *const size_t,
+    number_of_removed_outputs: size_t,
+    changed_outputs: *const size_t,
+    number_of_changed_outputs: size_t,
+    added_workspaces: *const size_t,
+    number_of_added_workspaces: size_t,
+    removed_workspaces: *const size_t,
+    number_of_removed_workspaces: size_t,
+    changed_workspaces: *const size_t,
+    number_of_changed_workspaces: size_t,
+    renamed_workspaces: *const AwcWorkspaceRename,
+    number_of_renamed_workspaces: size_t,
+    border_width_changed: bool,
+    colors_changed: bool,
+    display_error_cmd_changed: bool,
+    font_changed: bool,
+    inputs_changed: bool,
+    keyboards_changed: bool,
+    layout_changed: bool,
+    modes_changed: bool,
+    modifier_changed: bool,
+    profiles_changed: bool,
+    tabs_changed: bool,
+    window_rules_changed: bool,
 }
 
 // ### Helpers ###
 
-fn str_to_c_char(value: &str, descr: &str) -> Result<*const c_char, String> {
-    CString::new(value)
-        .map_err(|_| format!("{} must not contain 0 byte", descr))
-        .map(|s| s.into_raw() as *const c_char)
+/// Owns the strings and arrays referenced by the public structures, which
+/// are all freed together when the arena is dropped.
+#[derive(Default)]
+struct Arena {
+    strings: Vec<CString>,
+    slices: Vec<Box<dyn Any>>,
+}
+
+impl Arena {
+    fn str(&mut self, value: &str, descr: &str) -> Result<*const c_char, String> {
+        let c_string =
+            CString::new(value).map_err(|_| format!("{} must not contain 0 byte", descr))?;
+        // Moving the CString into the vector doesn't move its heap buffer
+        let ptr = c_string.as_ptr();
+        self.strings.push(c_string);
+        Ok(ptr)
+    }
+
+    fn slice<T: 'static>(&mut self, vec: Vec<T>) -> (*const T, usize) {
+        let boxed_slice = vec.into_boxed_slice();
+        let ptr = boxed_slice.as_ptr();
+        let len = boxed_slice.len();
+        self.slices.push(Box::new(boxed_slice));
+        (ptr, len)
+    }
+}
+
+extern "C" {
+    // Not exposed by the libc crate
+    fn fnmatch(pattern: *const c_char, string: *const c_char, flags: c_int) -> c_int;
+}
+
+fn glob_matches(pattern: &str, value: &str) -> bool {
+    match (CString::new(pattern), CString::new(value)) {
+        (Ok(pattern), Ok(value)) => unsafe { fnmatch(pattern.as_ptr(), value.as_ptr(), 0) == 0 },
+        _ => false,
+    }
 }
 
 fn vec_into_raw<T>(vec: Vec<T>) -> (*const T, usize) {
@@ -468,20 +1597,282 @@ fn vec_into_raw<T>(vec: Vec<T>) -> (*const T, usize) {
     (ptr, len)
 }
 
-fn load_config(path: &str, result: *mut AwcConfig) -> Result<(), String> {
-    let config: Config = serde_dhall::from_file(path)
-        .parse()
-        .map_err(|e| e.to_string())?;
+fn diagnostic_to_awc(diagnostic: Diagnostic) -> AwcDiagnostic {
+    // User-provided strings might contain 0 bytes, so strip them instead of
+    // failing to report the problem
+    let to_c_char =
+        |value: String| CString::new(value.replace('\0', "")).unwrap().into_raw() as *const c_char;
+    AwcDiagnostic {
+        severity: diagnostic.severity,
+        message: to_c_char(diagnostic.message),
+        path: to_c_char(diagnostic.path),
+    }
+}
+
+fn find_config_path(path: *const c_char) -> Result<String, String> {
+    if !path.is_null() {
+        Ok(unsafe { CStr::from_ptr(path) }
+            .to_string_lossy()
+            .into_owned())
+    } else {
+        xdg::BaseDirectories::with_prefix("awc")
+            .ok()
+            .and_then(|xdg_dirs| xdg_dirs.find_config_file("config.dhall"))
+            .and_then(|p| p.to_str().map(|p| p.to_string()))
+            .ok_or_else(|| "no config file or non-utf8 path".to_string())
+    }
+}
+
+/// Loads and validates the config. Only returns a handle if none of the
+/// returned diagnostics is an error.
+fn load_config(
+    path: *const c_char,
+    options: &AwcLoadOptions,
+) -> (Option<Box<AwcConfigHandle>>, Vec<Diagnostic>) {
+    let path_str = match find_config_path(path) {
+        Ok(path) => path,
+        Err(e) => return (None, vec![Diagnostic::error(String::new(), e)]),
+    };
+
+    let config_path = Path::new(&path_str);
+    let parsed = imports::Resolver::new(config_path, options.forbid_remote_imports).and_then(
+        |mut resolver| {
+            let staged_path = resolver.stage()?;
+            let fingerprint = resolver.fingerprint();
+            if let Some(config) = fingerprint.and_then(|f| cache::load(config_path, f)) {
+                return Ok(config);
+            }
+
+            let config = serde_dhall::from_file(staged_path)
+                .parse::<Config>()
+                .map_err(|e| resolver.explain_error(&e.to_string()))?;
+            if let Some(fingerprint) = fingerprint {
+                cache::store(config_path, fingerprint, &config);
+            }
+            Ok(config)
+        },
+    );
+
+    let config = match parsed {
+        Ok(config) => config,
+        Err(e) => return (None, vec![Diagnostic::error(String::new(), e)]),
+    };
+
+    let mut diagnostics = validate::validate(&config);
+    if diagnostics.iter().any(|d| d.is_error()) {
+        return (None, diagnostics);
+    }
+
+    let mut arena = Arena::default();
+    match config.to_awc(&mut arena) {
+        Ok(awc_config) => (
+            Some(Box::new(AwcConfigHandle {
+                config: awc_config,
+                source: config,
+                _arena: arena,
+            })),
+            diagnostics,
+        ),
+        Err(e) => {
+            diagnostics.push(Diagnostic::error(String::new(), e));
+            (None, diagnostics)
+        }
+    }
+}
+
+fn diagnostics_into_raw(diagnostics: Vec<Diagnostic>, target: *mut AwcDiagnostics) {
+    if target.is_null() {
+        return;
+    }
+    let converted_diagnostics = diagnostics.into_iter().map(diagnostic_to_awc).collect();
+    let (ptr, len) = vec_into_raw(converted_diagnostics);
     unsafe {
-        config.copy_to(result).map_err(|e| {
-            awc_config_free(result);
-            e
-        })
+        (*target).diagnostics = ptr;
+        (*target).number_of_diagnostics = len;
     }
 }
 
+/// Makes `result` a copy of the handle's config that owns the handle, so that
+/// the handle can be freed with `awc_config_free`.
+unsafe fn copy_owned(handle: Box<AwcConfigHandle>, result: *mut AwcConfig) {
+    let owner = Box::into_raw(handle);
+    std::ptr::copy_nonoverlapping(&(*owner).config, result, 1);
+    (*result).owner = owner;
+}
+
 // ### Public API ###
 
+/// Loads the config at `path` (or the user's config if `path` is NULL).
+/// Returns NULL if the config couldn't be loaded. All problems found are
+/// reported in `diagnostics`, which may be NULL if not of interest.
+///
+/// # Safety
+///
+/// `path` must point to a NULL-terminated string. The returned handle must be
+/// freed with `awc_config_handle_free` after use and `diagnostics` (if not
+/// NULL) with `awc_config_diagnostics_free`.
+#[no_mangle]
+pub unsafe extern "C" fn awc_config_handle_load(
+    path: *const c_char,
+    diagnostics: *mut AwcDiagnostics,
+) -> *mut AwcConfigHandle {
+    awc_config_handle_load_with_options(path, &AwcLoadOptions::default(), diagnostics)
+}
+
+/// Like `awc_config_handle_load`, but with non-default `options`.
+///
+/// # Safety
+///
+/// See `awc_config_handle_load`. `options` must not be NULL.
+#[no_mangle]
+pub unsafe extern "C" fn awc_config_handle_load_with_options(
+    path: *const c_char,
+    options: *const AwcLoadOptions,
+    diagnostics: *mut AwcDiagnostics,
+) -> *mut AwcConfigHandle {
+    let (handle, found) = load_config(path, &*options);
+    diagnostics_into_raw(found, diagnostics);
+    handle.map(Box::into_raw).unwrap_or_else(std::ptr::null_mut)
+}
+
+/// Returns the handle's config. It's valid as long as the handle is.
+///
+/// # Safety
+///
+/// `handle` must be a handle returned by `awc_config_handle_load` that
+/// hasn't been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn awc_config_handle_get(handle: *const AwcConfigHandle) -> *const AwcConfig {
+    &(*handle).config
+}
+
+/// Returns the config that applies to the described output or NULL if there
+/// is none. It's valid as long as the handle is. Unknown properties of the
+/// output can be NULL.
+///
+/// # Safety
+///
+/// `handle` must be a handle returned by `awc_config_handle_load` that
+/// hasn't been freed yet. `info` must not be NULL and its non-NULL strings
+/// must be NULL-terminated.
+#[no_mangle]
+pub unsafe extern "C" fn awc_config_handle_find_output(
+    handle: *const AwcConfigHandle,
+    info: *const AwcOutputInfo,
+) -> *const AwcOutputConfig {
+    let handle = &*handle;
+    match outputs::find(&handle.source.output_configs(), &(*info).to_info()) {
+        Some(i) => handle.config.outputs.add(i),
+        None => std::ptr::null(),
+    }
+}
+
+/// Returns the profile that matches the connected `outputs` best or NULL if
+/// no profile matches. A profile matches if each of its output configs
+/// matches a different connected output and every connected output is
+/// matched. If a profile matches, `configs[i]` is set to the profile's config
+/// for `outputs[i]`. Everything returned is valid as long as the handle is.
+///
+/// # Safety
+///
+/// `handle` must be a handle returned by `awc_config_handle_load` that
+/// hasn't been freed yet. `outputs` and `configs` must point to
+/// `number_of_outputs` elements, see `awc_config_handle_find_output` for the
+/// requirements on each `AwcOutputInfo`.
+#[no_mangle]
+pub unsafe extern "C" fn awc_config_handle_find_profile(
+    handle: *const AwcConfigHandle,
+    outputs: *const AwcOutputInfo,
+    number_of_outputs: size_t,
+    configs: *mut *const AwcOutputConfig,
+) -> *const AwcProfile {
+    if number_of_outputs == 0 {
+        return std::ptr::null();
+    }
+    let handle = &*handle;
+    let infos = std::slice::from_raw_parts(outputs, number_of_outputs)
+        .iter()
+        .map(|info| info.to_info())
+        .collect::<Vec<outputs::OutputInfo>>();
+    match outputs::find_profile(&handle.source.profiles, &infos) {
+        Some((i, assignment)) => {
+            let profile = &*handle.config.profiles.add(i);
+            let configs = std::slice::from_raw_parts_mut(configs, number_of_outputs);
+            for (config, j) in configs.iter_mut().zip(assignment) {
+                *config = profile.outputs.add(j);
+            }
+            profile
+        }
+        None => std::ptr::null(),
+    }
+}
+
+/// Returns the first keyboard config that matches the keyboard or NULL if
+/// there is none. It's valid as long as the handle is.
+///
+/// # Safety
+///
+/// `handle` must be a handle returned by `awc_config_handle_load` that
+/// hasn't been freed yet. `name` must be a NULL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn awc_config_handle_find_keyboard(
+    handle: *const AwcConfigHandle,
+    name: *const c_char,
+    type_: AwcKeyboardType,
+) -> *const AwcKeyboardConfig {
+    let name = CStr::from_ptr(name).to_str().unwrap_or("");
+    let handle = &*handle;
+    match inputs::find_keyboard(&handle.source.keyboard_configs(), name, type_) {
+        Some(i) => handle.config.keyboards.add(i),
+        None => std::ptr::null(),
+    }
+}
+
+/// Merges the settings of all input configs that match the device into
+/// `result`. Later configs take precedence. Returns whether any config
+/// matched. The matchers of `result` are always empty.
+///
+/// # Safety
+///
+/// `handle` must be a handle returned by `awc_config_handle_load` that
+/// hasn't been freed yet. `name` must be a NULL-terminated string and
+/// `result` must not be NULL.
+#[no_mangle]
+pub unsafe extern "C" fn awc_config_handle_find_input(
+    handle: *const AwcConfigHandle,
+    name: *const c_char,
+    type_: AwcInputType,
+    result: *mut AwcInputConfig,
+) -> bool {
+    let name = CStr::from_ptr(name).to_str().unwrap_or("");
+    match inputs::merge(&(*handle).source.inputs, name, type_) {
+        Some(merged) => {
+            *result = merged.settings_to_awc();
+            true
+        }
+        None => false,
+    }
+}
+
+/// Frees the handle and everything that has been borrowed from it.
+///
+/// # Safety
+///
+/// `handle` must be a handle returned by `awc_config_handle_load` or NULL.
+#[no_mangle]
+pub unsafe extern "C" fn awc_config_handle_free(handle: *mut AwcConfigHandle) {
+    if !handle.is_null() {
+        drop(Box::from_raw(handle));
+    }
+}
+
+/// Loads the config at `path` (or the user's config if `path` is NULL) and
+/// returns NULL on success. On failure, all errors are returned as one
+/// message and warnings are dropped. Use `awc_config_load_with_diagnostics`
+/// to get all of them.
+///
+/// Deprecated: use `awc_config_handle_load` instead.
+///
 /// # Safety
 ///
 /// `path` must point to a NULL-terminated string. The return value must be
@@ -493,79 +1884,220 @@ pub unsafe extern "C" fn awc_config_load(
     path: *const c_char,
     result: *mut AwcConfig,
 ) -> *const c_char {
-    let path_str = if !path.is_null() {
-        CStr::from_ptr(path).to_string_lossy().into_owned()
-    } else {
-        let config_path = xdg::BaseDirectories::with_prefix("awc")
-            .ok()
-            .and_then(|xdg_dirs| xdg_dirs.find_config_file("config.dhall"))
-            .and_then(|p| p.to_str().map(|p| p.to_string()));
-        match config_path {
-            Some(path) => path,
-            None => {
-                return CString::new("no config file or non-utf8 path")
-                    .map(|p| p.into_raw() as *const c_char)
-                    .unwrap()
-            }
-        }
-    };
+    let (handle, found) = load_config(path, &AwcLoadOptions::default());
+    if let Some(handle) = handle {
+        copy_owned(handle, result);
+        return std::ptr::null();
+    }
 
-    let types = include_str!("../Dhall/Types.dhall");
-    env::set_var("AWC_TYPES", &types);
+    let errors = found
+        .into_iter()
+        .filter(|d| d.is_error())
+        .map(|d| {
+            if d.path.is_empty() {
+                d.message
+            } else {
+                format!("{}: {}", d.path, d.message)
+            }
+        })
+        .collect::<Vec<String>>();
+    CString::new(errors.join("\n").replace('\0', ""))
+        .map(|p| p.into_raw() as *const c_char)
+        .unwrap()
+}
 
-    let result = load_config(&path_str, result);
-    env::remove_var("AWC_TYPES");
-    match result {
-        Err(desc) => CString::new(desc)
-            .map(|p| p.into_raw() as *const c_char)
-            // Error messages shouldn't contain any 0 bytes
-            .unwrap(),
-        Ok(_) => std::ptr::null(),
+/// Loads the config at `path` (or the user's config if `path` is NULL) and
+/// reports all problems found in `diagnostics`. Returns whether the config
+/// could be loaded, i.e. none of the diagnostics is an error.
+///
+/// Deprecated: use `awc_config_handle_load` instead.
+///
+/// # Safety
+///
+/// `path` must point to a NULL-terminated string. `diagnostics` must be freed
+/// with `awc_config_diagnostics_free` after use. If this function returns
+/// true, `result` must be freed with `awc_config_free` after use and the
+/// referenced `AwcConfig` structure must not be modified between this
+/// function's return and the free call.
+#[no_mangle]
+pub unsafe extern "C" fn awc_config_load_with_diagnostics(
+    path: *const c_char,
+    result: *mut AwcConfig,
+    diagnostics: *mut AwcDiagnostics,
+) -> bool {
+    let (handle, found) = load_config(path, &AwcLoadOptions::default());
+    diagnostics_into_raw(found, diagnostics);
+    match handle {
+        Some(handle) => {
+            copy_owned(handle, result);
+            true
+        }
+        None => false,
     }
 }
 
 /// # Safety
 ///
-/// This function only takes values that have been passed to a successful call
-/// of `awc_config_load` before.
+/// This function only takes values that have been passed to
+/// `awc_config_handle_load` or `awc_config_load_with_diagnostics` before.
 #[no_mangle]
-pub unsafe extern "C" fn awc_config_free(config: *mut AwcConfig) {
+pub unsafe extern "C" fn awc_config_diagnostics_free(diagnostics: *mut AwcDiagnostics) {
     Box::from_raw(std::slice::from_raw_parts_mut(
-        (*config).button_bindings as *mut AwcButtonBinding,
-        (*config).number_of_button_bindings,
-    ));
-
-    Box::from_raw(std::slice::from_raw_parts_mut(
-        (*config).key_bindings as *mut AwcKeyBinding,
-        (*config).number_of_key_bindings,
+        (*diagnostics).diagnostics as *mut AwcDiagnostic,
+        (*diagnostics).number_of_diagnostics,
     ))
     .iter()
-    .for_each(|binding| {
-        awc_config_str_free(binding.action.execute);
-        awc_config_str_free(binding.action.move_to);
-        awc_config_str_free(binding.action.view);
+    .for_each(|diagnostic| {
+        awc_config_str_free(diagnostic.message);
+        awc_config_str_free(diagnostic.path);
     });
+}
 
-    Box::from_raw(std::slice::from_raw_parts_mut(
-        (*config).keyboards as *mut AwcKeyboardConfig,
-        (*config).number_of_keyboards,
-    ))
-    .iter()
-    .for_each(|keyboard| awc_config_str_free(keyboard.layout));
+/// Computes what changed between the configs of the handles `old` and `new`.
+///
+/// # Safety
+///
+/// Both handles must be valid handles returned by `awc_config_handle_load`.
+/// `diff` must be freed with `awc_config_diff_free` after use.
+#[no_mangle]
+pub unsafe extern "C" fn awc_config_diff(
+    old: *const AwcConfigHandle,
+    new: *const AwcConfigHandle,
+    diff: *mut AwcConfigDiff,
+) {
+    let changes = diff::diff(&(*old).source, &(*new).source);
+    let converted_renamed_workspaces = changes
+        .renamed_workspaces
+        .iter()
+        .map(|(old, new)| AwcWorkspaceRename {
+            old: *old,
+            new: *new,
+        })
+        .collect();
 
-    Box::from_raw(std::slice::from_raw_parts_mut(
-        (*config).layout as *mut AwcLayoutOp,
-        (*config).number_of_layout_ops,
-    ));
+    let (added_button_bindings, number_of_added_button_bindings) =
+        vec_into_raw(changes.added_button_bindings);
+    let (removed_button_bindings, number_of_removed_button_bindings) =
+        vec_into_raw(changes.removed_button_bindings);
+    let (added_gesture_bindings, number_of_added_gesture_bindings) =
+        vec_into_raw(changes.added_gesture_bindings);
+    let (removed_gesture_bindings, number_of_removed_gesture_bindings) =
+        vec_into_raw(changes.removed_gesture_bindings);
+    let (added_key_bindings, number_of_added_key_bindings) =
+        vec_into_raw(changes.added_key_bindings);
+    let (removed_key_bindings, number_of_removed_key_bindings) =
+        vec_into_raw(changes.removed_key_bindings);
+    let (added_outputs, number_of_added_outputs) = vec_into_raw(changes.added_outputs);
+    let (removed_outputs, number_of_removed_outputs) = vec_into_raw(changes.removed_outputs);
+    let (changed_outputs, number_of_changed_outputs) = vec_into_raw(changes.changed_outputs);
+    let (added_workspaces, number_of_added_workspaces) = vec_into_raw(changes.added_workspaces);
+    let (removed_workspaces, number_of_removed_workspaces) =
+        vec_into_raw(changes.removed_workspaces);
+    let (changed_workspaces, number_of_changed_workspaces) =
+        vec_into_raw(changes.changed_workspaces);
+    let (renamed_workspaces, number_of_renamed_workspaces) =
+        vec_into_raw(converted_renamed_workspaces);
 
-    Box::from_raw(std::slice::from_raw_parts_mut(
-        (*config).outputs as *mut AwcOutputConfig,
-        (*config).number_of_outputs,
-    ))
-    .iter()
-    .for_each(|output| awc_config_str_free(output.name));
+    diff.write(AwcConfigDiff {
+        added_button_bindings,
+        number_of_added_button_bindings,
+        removed_button_bindings,
+        number_of_removed_button_bindings,
+        added_gesture_bindings,
+        number_of_added_gesture_bindings,
+        removed_gesture_bindings,
+        number_of_removed_gesture_bindings,
+        added_key_bindings,
+        number_of_added_key_bindings,
+        removed_key_bindings,
+        number_of_removed_key_bindings,
+        added_outputs,
+        number_of_added_outputs,
+        removed_outputs,
+        number_of_removed_outputs,
+        changed_outputs,
+        number_of_changed_outputs,
+        added_workspaces,
+        number_of_added_workspaces,
+        removed_workspaces,
+        number_of_removed_workspaces,
+        changed_workspaces,
+        number_of_changed_workspaces,
+        renamed_workspaces,
+        number_of_renamed_workspaces,
+        border_width_changed: changes.border_width_changed,
+        colors_changed: changes.colors_changed,
+        display_error_cmd_changed: changes.display_error_cmd_changed,
+        font_changed: changes.font_changed,
+        inputs_changed: changes.inputs_changed,
+        keyboards_changed: changes.keyboards_changed,
+        layout_changed: changes.layout_changed,
+        modes_changed: changes.modes_changed,
+        profiles_changed: changes.profiles_changed,
+        modifier_changed: changes.modifier_changed,
+        tabs_changed: changes.tabs_changed,
+        window_rules_changed: changes.window_rules_changed,
+    });
+}
+
+/// # Safety
+///
+/// This function only takes values that have been passed to
+/// `awc_config_diff` before.
+#[no_mangle]
+pub unsafe extern "C" fn awc_config_diff_free(diff: *mut AwcConfigDiff) {
+    let diff = &*diff;
+    for (ptr, len) in [
+        (
+            diff.added_button_bindings,
+            diff.number_of_added_button_bindings,
+        ),
+        (
+            diff.removed_button_bindings,
+            diff.number_of_removed_button_bindings,
+        ),
+        (
+            diff.added_gesture_bindings,
+            diff.number_of_added_gesture_bindings,
+        ),
+        (
+            diff.removed_gesture_bindings,
+            diff.number_of_removed_gesture_bindings,
+        ),
+        (diff.added_key_bindings, diff.number_of_added_key_bindings),
+        (
+            diff.removed_key_bindings,
+            diff.number_of_removed_key_bindings,
+        ),
+        (diff.added_outputs, diff.number_of_added_outputs),
+        (diff.removed_outputs, diff.number_of_removed_outputs),
+        (diff.changed_outputs, diff.number_of_changed_outputs),
+        (diff.added_workspaces, diff.number_of_added_workspaces),
+        (diff.removed_workspaces, diff.number_of_removed_workspaces),
+        (diff.changed_workspaces, diff.number_of_changed_workspaces),
+    ] {
+        drop(Box::from_raw(std::slice::from_raw_parts_mut(
+            ptr as *mut size_t,
+            len,
+        )));
+    }
+    drop(Box::from_raw(std::slice::from_raw_parts_mut(
+        diff.renamed_workspaces as *mut AwcWorkspaceRename,
+        diff.number_of_renamed_workspaces,
+    )));
+}
 
-    awc_config_str_free((*config).display_error_cmd);
+/// Frees everything referenced by `config`.
+///
+/// # Safety
+///
+/// This function only takes values that have been passed to a successful call
+/// of `awc_config_load` or `awc_config_load_with_diagnostics` before.
+#[no_mangle]
+pub unsafe extern "C" fn awc_config_free(config: *mut AwcConfig) {
+    let owner = (*config).owner;
+    (*config).owner = std::ptr::null_mut();
+    awc_config_handle_free(owner);
 }
 
 #[no_mangle]
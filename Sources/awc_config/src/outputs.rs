@@ -0,0 +1,124 @@
+use crate::{glob_matches, OutputConfig, OutputMatch, Profile};
+
+/// What is known about a connected output.
+pub(crate) struct OutputInfo<'a> {
+    pub name: &'a str,
+    pub make: &'a str,
+    pub model: &'a str,
+    pub serial: &'a str,
+    pub description: &'a str,
+}
+
+/// Returns the index of the output config that applies to the given output.
+/// If several configs match, the most specific one wins. Ties go to the
+/// config that comes first.
+pub(crate) fn find(outputs: &[OutputConfig], info: &OutputInfo) -> Option<usize> {
+    let mut best: Option<(usize, u32)> = None;
+    for (i, output) in outputs.iter().enumerate() {
+        if let Some(score) = score(output, info) {
+            if best.map_or(true, |(_, best_score)| score > best_score) {
+                best = Some((i, score));
+            }
+        }
+    }
+    best.map(|(i, _)| i)
+}
+
+/// Returns the index of the profile that matches the connected outputs best,
+/// together with the index of each connected output's config in that
+/// profile. A profile matches if each of its configs matches a different
+/// connected output and no connected output is left over. Ties go to the
+/// profile that comes first.
+pub(crate) fn find_profile(
+    profiles: &[Profile],
+    infos: &[OutputInfo],
+) -> Option<(usize, Vec<usize>)> {
+    let mut best: Option<(usize, u32, Vec<usize>)> = None;
+    for (i, profile) in profiles.iter().enumerate() {
+        if profile.outputs.len() != infos.len() {
+            continue;
+        }
+        let mut used = vec![false; profile.outputs.len()];
+        if let Some((score, assignment)) = assign(&profile.outputs, infos, &mut used) {
+            if best
+                .as_ref()
+                .map_or(true, |(_, best_score, _)| score > *best_score)
+            {
+                best = Some((i, score, assignment));
+            }
+        }
+    }
+    best.map(|(i, _, assignment)| (i, assignment))
+}
+
+/// Assigns each output in `infos` a different, unused config such that the
+/// sum of all scores is maximal. Profiles are small, so trying all
+/// assignments is fine.
+fn assign(
+    outputs: &[OutputConfig],
+    infos: &[OutputInfo],
+    used: &mut [bool],
+) -> Option<(u32, Vec<usize>)> {
+    let (info, rest) = match infos.split_first() {
+        Some(split) => split,
+        None => return Some((0, Vec::new())),
+    };
+    let mut best: Option<(u32, Vec<usize>)> = None;
+    for (i, output) in outputs.iter().enumerate() {
+        if used[i] {
+            continue;
+        }
+        if let Some(score) = score(output, info) {
+            used[i] = true;
+            if let Some((rest_score, rest_assignment)) = assign(outputs, rest, used) {
+                let total = score + rest_score;
+                if best
+                    .as_ref()
+                    .map_or(true, |(best_score, _)| total > *best_score)
+                {
+                    let mut assignment = vec![i];
+                    assignment.extend(rest_assignment);
+                    best = Some((total, assignment));
+                }
+            }
+            used[i] = false;
+        }
+    }
+    best
+}
+
+/// How well the config matches the output, `None` if it doesn't.
+fn score(output: &OutputConfig, info: &OutputInfo) -> Option<u32> {
+    if output.matches.iter().all(|m| matches(m, info)) {
+        Some(output.matches.iter().map(specificity).sum())
+    } else {
+        None
+    }
+}
+
+fn matches(output_match: &OutputMatch, info: &OutputInfo) -> bool {
+    match output_match {
+        OutputMatch::Connector(pattern) => glob_matches(pattern, info.name),
+        OutputMatch::Make(make) => make == info.make,
+        OutputMatch::Model(model) => model == info.model,
+        OutputMatch::Serial(serial) => serial == info.serial,
+        OutputMatch::Description(part) => info.description.contains(part.as_str()),
+    }
+}
+
+/// How precisely a matcher identifies an output. A serial number identifies
+/// one specific monitor, whereas a connector can be anything plugged in.
+fn specificity(output_match: &OutputMatch) -> u32 {
+    match output_match {
+        OutputMatch::Connector(pattern) if is_glob(pattern) => 1,
+        OutputMatch::Connector(_) => 2,
+        OutputMatch::Make(_) => 4,
+        OutputMatch::Model(_) => 8,
+        OutputMatch::Description(_) => 8,
+        OutputMatch::Serial(_) => 16,
+    }
+}
+
+fn is_glob(pattern: &str) -> bool {
+    pattern.contains(|c| matches!(c, '*' | '?' | '['))
+}
@@ -0,0 +1,548 @@
+use std::collections::HashMap;
+
+use crate::xkb;
+use crate::{
+    Action, AwcButton, AwcGesture, AwcGrid, AwcModifier, AwcSeverity, ButtonAction, Config, Key,
+    KeyBinding, KeyboardConfig, LayoutOp, OutputConfig, Pattern, WindowEffect, WindowMatch,
+};
+
+/// A problem found in a config. Errors prevent the config from being used,
+/// warnings are only reported.
+#[derive(Debug)]
+pub(crate) struct Diagnostic {
+    pub severity: AwcSeverity,
+    pub message: String,
+    pub path: String,
+}
+
+impl Diagnostic {
+    pub fn error(path: String, message: String) -> Diagnostic {
+        Diagnostic {
+            severity: AwcSeverity::Error,
+            message,
+            path,
+        }
+    }
+
+    pub fn warning(path: String, message: String) -> Diagnostic {
+        Diagnostic {
+            severity: AwcSeverity::Warning,
+            message,
+            path,
+        }
+    }
+
+    pub fn is_error(&self) -> bool {
+        matches!(self.severity, AwcSeverity::Error)
+    }
+}
+
+/// Semantic checks that go beyond what Dhall's type system can express.
+pub(crate) fn validate(config: &Config) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    if config.workspaces.is_empty() {
+        diagnostics.push(Diagnostic::error(
+            "workspaces".to_string(),
+            "at least one workspace is required".to_string(),
+        ));
+    }
+
+    let mut seen_tags: HashMap<&str, usize> = HashMap::new();
+    for (i, tag) in config.workspaces.iter().enumerate() {
+        if let Some(first) = seen_tags.insert(tag, i) {
+            diagnostics.push(Diagnostic::error(
+                format!("workspaces[{}]", i),
+                format!("tag `{}` is already used by workspaces[{}]", tag, first),
+            ));
+        }
+    }
+
+    let mut seen_settings: HashMap<&str, usize> = HashMap::new();
+    for (i, settings) in config.workspace_settings.iter().enumerate() {
+        let path = format!("workspaceSettings[{}]", i);
+        if let Some(first) = seen_settings.insert(&settings.tag, i) {
+            diagnostics.push(Diagnostic::error(
+                format!("{}.tag", path),
+                format!(
+                    "workspace `{}` already has settings in workspaceSettings[{}]",
+                    settings.tag, first
+                ),
+            ));
+        }
+        check_workspace(
+            config,
+            &settings.tag,
+            &format!("{}.tag", path),
+            &mut diagnostics,
+        );
+        if let Some(layout) = &settings.layout {
+            validate_layout(layout, &format!("{}.layout", path), &mut diagnostics);
+        }
+        if let Some(n) = settings.output {
+            check_output(config, n, &format!("{}.output", path), &mut diagnostics);
+        }
+    }
+
+    validate_layout(&config.layout, "layout", &mut diagnostics);
+
+    for (i, keyboard) in config.keyboards.iter().enumerate() {
+        check_keymap(
+            &keyboard.to_keyboard_config(),
+            &format!("keyboards[{}]", i),
+            &mut diagnostics,
+        );
+    }
+
+    for (i, keyboard) in config.keyboard_settings.iter().enumerate() {
+        if keyboard.matches.is_empty() {
+            diagnostics.push(Diagnostic::error(
+                format!("keyboardSettings[{}].matches", i),
+                "keyboard config without matchers, use Name \"*\" to match any keyboard"
+                    .to_string(),
+            ));
+        }
+        check_keymap(
+            keyboard,
+            &format!("keyboardSettings[{}]", i),
+            &mut diagnostics,
+        );
+        if keyboard.repeat_rate > i32::MAX as u32 || keyboard.repeat_delay > i32::MAX as u32 {
+            diagnostics.push(Diagnostic::error(
+                format!("keyboardSettings[{}]", i),
+                "repeat rate or delay is too large".to_string(),
+            ));
+        }
+    }
+
+    for (i, input) in config.inputs.iter().enumerate() {
+        if input.matches.is_empty() {
+            diagnostics.push(Diagnostic::error(
+                format!("inputs[{}].matches", i),
+                "input config without matchers, use Name \"*\" to match any device".to_string(),
+            ));
+        }
+        if matches!(input.accel_speed, Some(speed) if !(-1.0..=1.0).contains(&speed)) {
+            diagnostics.push(Diagnostic::error(
+                format!("inputs[{}].accelSpeed", i),
+                "acceleration speed must be between -1 and 1".to_string(),
+            ));
+        }
+    }
+
+    for (i, output) in config.outputs.iter().enumerate() {
+        if output.scale <= 0.0 {
+            diagnostics.push(Diagnostic::error(
+                format!("outputs[{}].scale", i),
+                format!("scale of output {} must be greater than 0", output.name),
+            ));
+        }
+    }
+
+    validate_outputs(&config.output_settings, "outputSettings", &mut diagnostics);
+    for (i, output) in config.output_settings.iter().enumerate() {
+        // In a profile, several outputs with the same matchers are fine, as
+        // each of them matches a different output
+        if let Some(j) = config.output_settings[..i]
+            .iter()
+            .position(|o| !o.matches.is_empty() && o.matches == output.matches)
+        {
+            diagnostics.push(Diagnostic::warning(
+                format!("outputSettings[{}].matches", i),
+                format!("never applies, outputSettings[{}] has the same matchers", j),
+            ));
+        }
+    }
+
+    let mut seen_profiles: HashMap<&str, usize> = HashMap::new();
+    for (i, profile) in config.profiles.iter().enumerate() {
+        if let Some(first) = seen_profiles.insert(&profile.name, i) {
+            diagnostics.push(Diagnostic::error(
+                format!("profiles[{}].name", i),
+                format!(
+                    "name `{}` is already used by profiles[{}]",
+                    profile.name, first
+                ),
+            ));
+        }
+        let path = format!("profiles[{}].outputs", i);
+        if profile.outputs.is_empty() {
+            diagnostics.push(Diagnostic::error(
+                path,
+                "a profile needs at least one output".to_string(),
+            ));
+        } else {
+            validate_outputs(&profile.outputs, &path, &mut diagnostics);
+        }
+    }
+
+    validate_key_bindings(
+        config,
+        &config.key_bindings,
+        "keyBindings",
+        &mut diagnostics,
+    );
+
+    let mut seen_modes: HashMap<&str, usize> = HashMap::new();
+    for (i, mode) in config.modes.iter().enumerate() {
+        if let Some(first) = seen_modes.insert(&mode.name, i) {
+            diagnostics.push(Diagnostic::error(
+                format!("modes[{}].name", i),
+                format!(
+                    "mode `{}` is already defined by modes[{}]",
+                    mode.name, first
+                ),
+            ));
+        }
+        validate_key_bindings(
+            config,
+            &mode.key_bindings,
+            &format!("modes[{}].keyBindings", i),
+            &mut diagnostics,
+        );
+    }
+
+    let mut seen_buttons: HashMap<(u8, AwcButton), usize> = HashMap::new();
+    for (i, binding) in config.button_bindings.iter().enumerate() {
+        let path = format!("buttonBindings[{}].action", i);
+        match &binding.action {
+            ButtonAction::Run(action) => validate_action(config, action, &path, &mut diagnostics),
+            _ if binding.button.is_scroll() => diagnostics.push(Diagnostic::error(
+                path,
+                "scrolling can only run actions, use ButtonAction.Run".to_string(),
+            )),
+            _ => (),
+        }
+        let mods = modifier_mask(&binding.mods);
+        if let Some(first) = seen_buttons.insert((mods, binding.button), i) {
+            diagnostics.push(Diagnostic::warning(
+                format!("buttonBindings[{}]", i),
+                format!(
+                    "same modifiers and button as buttonBindings[{}], only one of them will be used",
+                    first
+                ),
+            ));
+        }
+    }
+
+    let mut seen_gestures: HashMap<(u8, u8, AwcGesture), usize> = HashMap::new();
+    for (i, binding) in config.gesture_bindings.iter().enumerate() {
+        // Two-finger swipes are scrolling and never reported as swipes
+        let min_fingers = if binding.gesture.is_swipe() { 3 } else { 2 };
+        if binding.fingers < min_fingers {
+            diagnostics.push(Diagnostic::error(
+                format!("gestureBindings[{}].fingers", i),
+                format!(
+                    "{:?} needs at least {} fingers",
+                    binding.gesture, min_fingers
+                ),
+            ));
+        }
+        validate_action(
+            config,
+            &binding.action,
+            &format!("gestureBindings[{}].action", i),
+            &mut diagnostics,
+        );
+        let mods = modifier_mask(&binding.mods);
+        if let Some(first) = seen_gestures.insert((mods, binding.fingers, binding.gesture), i) {
+            diagnostics.push(Diagnostic::warning(
+                format!("gestureBindings[{}]", i),
+                format!(
+                    "same modifiers and gesture as gestureBindings[{}], only one of them will be used",
+                    first
+                ),
+            ));
+        }
+    }
+
+    for (i, rule) in config.window_rules.iter().enumerate() {
+        if rule.matches.is_empty() {
+            diagnostics.push(Diagnostic::warning(
+                format!("windowRules[{}].matches", i),
+                "rule without matchers applies to every window".to_string(),
+            ));
+        }
+        for (j, window_match) in rule.matches.iter().enumerate() {
+            let pattern = match window_match {
+                WindowMatch::AppId(pattern)
+                | WindowMatch::Title(pattern)
+                | WindowMatch::Class(pattern) => pattern,
+            };
+            if let Pattern::Regex(regex) = pattern {
+                if let Err(e) = check_regex(regex) {
+                    diagnostics.push(Diagnostic::error(
+                        format!("windowRules[{}].matches[{}]", i, j),
+                        format!("invalid regular expression `{}`: {}", regex, e),
+                    ));
+                }
+            }
+        }
+        for (j, effect) in rule.effects.iter().enumerate() {
+            let path = format!("windowRules[{}].effects[{}]", i, j);
+            match effect {
+                WindowEffect::Workspace(ws) => check_workspace(config, ws, &path, &mut diagnostics),
+                WindowEffect::Output(n) => check_output(config, *n, &path, &mut diagnostics),
+                _ => (),
+            }
+        }
+    }
+
+    diagnostics
+}
+
+fn validate_key_bindings(
+    config: &Config,
+    bindings: &[KeyBinding],
+    path: &str,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let mut seen_bindings: HashMap<(u8, &Key), usize> = HashMap::new();
+    for (i, binding) in bindings.iter().enumerate() {
+        if let Key::Sym(sym) = &binding.key {
+            if !xkb::is_known_keysym(sym) {
+                diagnostics.push(Diagnostic::error(
+                    format!("{}[{}].key", path, i),
+                    format!("unknown key symbol `{}`", sym),
+                ));
+            }
+        }
+        validate_action(
+            config,
+            &binding.action,
+            &format!("{}[{}].action", path, i),
+            diagnostics,
+        );
+
+        let mods = modifier_mask(&binding.mods);
+        if let Some(first) = seen_bindings.insert((mods, &binding.key), i) {
+            diagnostics.push(Diagnostic::warning(
+                format!("{}[{}]", path, i),
+                format!(
+                    "same modifiers and key as {}[{}], only one of them will be used",
+                    path, first
+                ),
+            ));
+        }
+    }
+}
+
+fn validate_action(
+    config: &Config,
+    action: &Action,
+    path: &str,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    match action {
+        Action::GreedyView(ws)
+        | Action::MoveTo(ws)
+        | Action::SwapWorkspaceTagWith(ws)
+        | Action::View(ws) => check_workspace(config, ws, path, diagnostics),
+        Action::FocusOutput(n) | Action::MoveToOutput(n) => {
+            check_output(config, *n, path, diagnostics)
+        }
+        Action::EnterMode(mode) => {
+            if !config.modes.iter().any(|m| &m.name == mode) {
+                diagnostics.push(Diagnostic::error(
+                    path.to_string(),
+                    format!("mode `{}` is not in `modes`", mode),
+                ))
+            }
+        }
+        Action::Sequence(actions) => {
+            for (i, action) in actions.iter().enumerate() {
+                validate_action(config, action, &format!("{}[{}]", path, i), diagnostics);
+            }
+        }
+        Action::Focus(0) => diagnostics.push(Diagnostic::warning(
+            path.to_string(),
+            "views are 1-indexed, Focus 0 does nothing".to_string(),
+        )),
+        Action::SwitchVT(0) => diagnostics.push(Diagnostic::warning(
+            path.to_string(),
+            "virtual terminals are 1-indexed, SwitchVT 0 does nothing".to_string(),
+        )),
+        _ => (),
+    }
+}
+
+fn check_workspace(config: &Config, tag: &str, path: &str, diagnostics: &mut Vec<Diagnostic>) {
+    if !config.workspaces.iter().any(|ws| ws == tag) {
+        diagnostics.push(Diagnostic::warning(
+            path.to_string(),
+            format!("workspace `{}` is not in `workspaces`", tag),
+        ));
+    }
+}
+
+fn validate_layout(ops: &[LayoutOp], path: &str, diagnostics: &mut Vec<Diagnostic>) {
+    for (i, op) in ops.iter().enumerate() {
+        let message = match op {
+            LayoutOp::Grid(AwcGrid::Columns(0)) => "a grid needs at least one column",
+            LayoutOp::Grid(AwcGrid::AspectRatio(ratio)) if *ratio <= 0.0 || ratio.is_nan() => {
+                "the aspect ratio of a grid must be greater than 0"
+            }
+            LayoutOp::Spiral { ratio } if !(0.0..1.0).contains(ratio) || *ratio == 0.0 => {
+                "the ratio of a spiral must be between 0 and 1"
+            }
+            LayoutOp::Named(name) if name.trim().is_empty() => "a layout name must not be empty",
+            _ => continue,
+        };
+        diagnostics.push(Diagnostic::error(
+            format!("{}[{}]", path, i),
+            message.to_string(),
+        ));
+    }
+}
+
+fn check_keymap(keyboard: &KeyboardConfig, path: &str, diagnostics: &mut Vec<Diagnostic>) {
+    if !xkb::can_compile_keymap(
+        &keyboard.layout,
+        &keyboard.variant,
+        &keyboard.model,
+        &keyboard.options,
+    ) {
+        diagnostics.push(Diagnostic::error(
+            path.to_string(),
+            format!(
+                "invalid keymap (layout `{}`, variant `{}`, model `{}`, options `{}`)",
+                keyboard.layout, keyboard.variant, keyboard.model, keyboard.options
+            ),
+        ));
+    }
+}
+
+fn validate_outputs(outputs: &[OutputConfig], path: &str, diagnostics: &mut Vec<Diagnostic>) {
+    for (i, output) in outputs.iter().enumerate() {
+        if output.matches.is_empty() {
+            diagnostics.push(Diagnostic::error(
+                format!("{}[{}].matches", path, i),
+                "output config without matchers, use Connector \"*\" to match any output"
+                    .to_string(),
+            ));
+        }
+        if output.scale <= 0.0 {
+            diagnostics.push(Diagnostic::error(
+                format!("{}[{}].scale", path, i),
+                "scale must be greater than 0".to_string(),
+            ));
+        }
+        if let Some(mode) = &output.mode {
+            if mode.width == 0
+                || mode.height == 0
+                || mode.width > i32::MAX as u32
+                || mode.height > i32::MAX as u32
+            {
+                diagnostics.push(Diagnostic::error(
+                    format!("{}[{}].mode", path, i),
+                    format!("mode {}x{} is invalid", mode.width, mode.height),
+                ));
+            }
+            if matches!(mode.refresh, Some(refresh) if refresh <= 0.0) {
+                diagnostics.push(Diagnostic::error(
+                    format!("{}[{}].mode.refresh", path, i),
+                    "refresh rate must be greater than 0".to_string(),
+                ));
+            }
+        }
+    }
+}
+
+fn check_output(config: &Config, n: u8, path: &str, diagnostics: &mut Vec<Diagnostic>) {
+    let number_of_outputs = config.output_configs().len();
+    if n == 0 {
+        diagnostics.push(Diagnostic::error(
+            path.to_string(),
+            "outputs are 1-indexed, output 0 doesn't exist".to_string(),
+        ));
+    } else if number_of_outputs > 0 && usize::from(n) > number_of_outputs {
+        diagnostics.push(Diagnostic::warning(
+            path.to_string(),
+            format!(
+                "output {} is beyond the {} configured outputs",
+                n, number_of_outputs
+            ),
+        ));
+    }
+}
+
+/// Finds syntax errors in a regular expression. The compositor compiles it
+/// with its own regex engine, so this only catches mistakes that no dialect
+/// accepts.
+fn check_regex(regex: &str) -> Result<(), String> {
+    let mut chars = regex.chars().peekable();
+    let mut open_groups = 0usize;
+    // Whether a quantifier at this point has something to repeat
+    let mut repeatable = false;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('Q') => {
+                    // Quoted until `\E`
+                    while let Some(c) = chars.next() {
+                        if c == '\\' && chars.peek() == Some(&'E') {
+                            chars.next();
+                            break;
+                        }
+                    }
+                }
+                Some(_) => (),
+                None => return Err("trailing backslash".to_string()),
+            },
+            '[' => {
+                // A `]` right at the start is part of the class
+                if chars.peek() == Some(&'^') {
+                    chars.next();
+                }
+                if chars.peek() == Some(&']') {
+                    chars.next();
+                }
+                loop {
+                    match chars.next() {
+                        Some('\\') => {
+                            chars.next();
+                        }
+                        Some(']') => break,
+                        Some(_) => (),
+                        None => return Err("unterminated character class".to_string()),
+                    }
+                }
+            }
+            '(' => {
+                if chars.peek() == Some(&'?') {
+                    chars.next();
+                    if chars.peek() == Some(&'#') {
+                        if !chars.any(|c| c == ')') {
+                            return Err("unterminated comment".to_string());
+                        }
+                        continue;
+                    }
+                }
+                open_groups += 1;
+                repeatable = false;
+                continue;
+            }
+            ')' if open_groups == 0 => return Err("unmatched `)`".to_string()),
+            ')' => open_groups -= 1,
+            '|' => {
+                repeatable = false;
+                continue;
+            }
+            '*' | '+' | '?' if !repeatable => {
+                return Err(format!("`{}` doesn't follow anything it could repeat", c))
+            }
+            _ => (),
+        }
+        repeatable = true;
+    }
+
+    if open_groups > 0 {
+        return Err("unclosed group".to_string());
+    }
+    Ok(())
+}
+
+fn modifier_mask(mods: &[AwcModifier]) -> u8 {
+    mods.iter().fold(0, |mask, m| mask | (1 << (*m as u8)))
+}
@@ -0,0 +1,79 @@
+//! Just enough of xkbcommon to check key symbols and keymaps while loading a
+//! config, so that mistakes are reported before the compositor uses them.
+
+use std::ffi::{c_void, CString};
+use std::os::raw::{c_char, c_int};
+
+#[repr(C)]
+struct XkbRuleNames {
+    rules: *const c_char,
+    model: *const c_char,
+    layout: *const c_char,
+    variant: *const c_char,
+    options: *const c_char,
+}
+
+const XKB_KEY_NO_SYMBOL: u32 = 0;
+const XKB_KEYSYM_NO_FLAGS: c_int = 0;
+const XKB_CONTEXT_NO_FLAGS: c_int = 0;
+const XKB_KEYMAP_COMPILE_NO_FLAGS: c_int = 0;
+
+#[link(name = "xkbcommon")]
+extern "C" {
+    fn xkb_keysym_from_name(name: *const c_char, flags: c_int) -> u32;
+    fn xkb_context_new(flags: c_int) -> *mut c_void;
+    fn xkb_context_unref(context: *mut c_void);
+    fn xkb_keymap_new_from_names(
+        context: *mut c_void,
+        names: *const XkbRuleNames,
+        flags: c_int,
+    ) -> *mut c_void;
+    fn xkb_keymap_unref(keymap: *mut c_void);
+}
+
+/// Whether xkbcommon knows a key symbol with that name. Like the compositor,
+/// the lookup is case-sensitive.
+pub(crate) fn is_known_keysym(name: &str) -> bool {
+    match CString::new(name) {
+        Ok(name) => unsafe {
+            xkb_keysym_from_name(name.as_ptr(), XKB_KEYSYM_NO_FLAGS) != XKB_KEY_NO_SYMBOL
+        },
+        Err(_) => false,
+    }
+}
+
+/// Whether a keymap can be compiled from the given names. Empty names select
+/// XKB's defaults.
+pub(crate) fn can_compile_keymap(layout: &str, variant: &str, model: &str, options: &str) -> bool {
+    let names = match (
+        CString::new(layout),
+        CString::new(variant),
+        CString::new(model),
+        CString::new(options),
+    ) {
+        (Ok(layout), Ok(variant), Ok(model), Ok(options)) => [layout, variant, model, options],
+        _ => return false,
+    };
+    let rule_names = XkbRuleNames {
+        rules: std::ptr::null(),
+        layout: names[0].as_ptr(),
+        variant: names[1].as_ptr(),
+        model: names[2].as_ptr(),
+        options: names[3].as_ptr(),
+    };
+    unsafe {
+        let context = xkb_context_new(XKB_CONTEXT_NO_FLAGS);
+        if context.is_null() {
+            // Nothing can be checked without a context, let the compositor
+            // report the problem
+            return true;
+        }
+        let keymap = xkb_keymap_new_from_names(context, &rule_names, XKB_KEYMAP_COMPILE_NO_FLAGS);
+        let compiled = !keymap.is_null();
+        if compiled {
+            xkb_keymap_unref(keymap);
+        }
+        xkb_context_unref(context);
+        compiled
+    }
+}
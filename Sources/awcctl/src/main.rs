@@ -15,6 +15,13 @@ enum Request {
     SetLayout { layout_number: u8 },
 }
 
+/// Like `Request::SetLayout`, but selects the layout by its description.
+#[derive(Serialize)]
+#[serde(tag = "cmd", rename = "set_layout")]
+struct SetLayoutByName {
+    name: String,
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 struct ViewBox {
     x: isize,
@@ -57,6 +64,21 @@ fn get_arg_matches() -> clap::ArgMatches {
                 .arg(clap::Arg::new("new-tag").required(true)),
         )
         .subcommand(clap::Command::new("select-layout"))
+        .subcommand(
+            clap::Command::new("set-layout")
+                .arg(
+                    clap::Arg::new("layout")
+                        .required_unless_present("name")
+                        .help("Number of the layout"),
+                )
+                .arg(
+                    clap::Arg::new("name")
+                        .long("name")
+                        .takes_value(true)
+                        .conflicts_with("layout")
+                        .help("Description of the layout"),
+                ),
+        )
         .subcommand_required(true)
         .get_matches()
 }
@@ -88,9 +110,9 @@ fn read_size(stream: &mut UnixStream) -> Result<u32, Box<dyn std::error::Error>>
     Ok(u32::from_ne_bytes(size_buf))
 }
 
-fn send_request(
+fn send_request<R: Serialize>(
     stream: &mut UnixStream,
-    request: &Request,
+    request: &R,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let serialized = serde_json::to_string(&request)?;
     let size: u32 = serialized.as_bytes().len() as u32;
@@ -198,6 +220,32 @@ fn select_layout(stream: &mut UnixStream, menu: &str) -> Result<(), Box<dyn std:
     }
 }
 
+fn set_layout(
+    stream: &mut UnixStream,
+    layout_number: u8,
+) -> Result<(), Box<dyn std::error::Error>> {
+    send_request(stream, &Request::SetLayout { layout_number })?;
+    let result: String = read_response(stream)?;
+    if result == "ok" {
+        Ok(())
+    } else {
+        Err(result.into())
+    }
+}
+
+fn set_layout_by_name(
+    stream: &mut UnixStream,
+    name: String,
+) -> Result<(), Box<dyn std::error::Error>> {
+    send_request(stream, &SetLayoutByName { name })?;
+    let result: String = read_response(stream)?;
+    if result == "ok" {
+        Ok(())
+    } else {
+        Err(result.into())
+    }
+}
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let args = get_arg_matches();
     let json = args.is_present("json");
@@ -217,6 +265,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             rename_matches.value_of_t_or_exit("new-tag"),
         )?,
         Some(("select-layout", _)) => select_layout(&mut socket, args.value_of("menu").unwrap())?,
+        Some(("set-layout", set_layout_matches)) => match set_layout_matches.value_of("name") {
+            Some(name) => set_layout_by_name(&mut socket, name.to_string())?,
+            None => set_layout(&mut socket, set_layout_matches.value_of_t_or_exit("layout"))?,
+        },
         _ => {}
     }
 
@@ -18,8 +18,455 @@ public final class ConfigTests: XCTestCase {
         }
     }
 
+    func testButtonActions() {
+        withConfig(Bundle.module, "button_actions") {
+            XCTAssertEqual($0.number_of_button_bindings, 3)
+            XCTAssertEqual($0.button_bindings[0].button, ScrollUp)
+            XCTAssertEqual($0.button_bindings[0].action, Run)
+            XCTAssertTrue($0.button_bindings[0].run.pointee.focus_up)
+            XCTAssertEqual($0.button_bindings[1].button, Middle)
+            XCTAssertTrue($0.button_bindings[1].run.pointee.close)
+            XCTAssertEqual($0.button_bindings[2].action, Move)
+            XCTAssertNil($0.button_bindings[2].run)
+        }
+    }
+
+    func testGestureBindings() {
+        withConfig(Bundle.module, "gestures") {
+            XCTAssertEqual($0.number_of_gesture_bindings, 2)
+            XCTAssertEqual($0.gesture_bindings[0].number_of_mods, 0)
+            XCTAssertEqual($0.gesture_bindings[0].fingers, 3)
+            XCTAssertEqual($0.gesture_bindings[0].gesture, AwcGesture_SwipeLeft)
+            XCTAssertEqual(String(cString: $0.gesture_bindings[0].action.view), "2")
+            XCTAssertEqual($0.gesture_bindings[1].mods[0], Logo)
+            XCTAssertEqual($0.gesture_bindings[1].gesture, AwcGesture_PinchIn)
+            XCTAssertTrue($0.gesture_bindings[1].action.close)
+        }
+    }
+
+    func testRelativeImport() {
+        withConfig(Bundle.module, "relative_import") {
+            XCTAssertEqual($0.number_of_button_bindings, 1)
+            XCTAssertEqual($0.button_bindings[0].number_of_mods, 1)
+        }
+    }
+
+    func testForbiddenRemoteImport() {
+        let configPath = Bundle.module.path(forResource: "remote_import", ofType: "dhall", inDirectory: "Fixtures")!
+        var options = AwcLoadOptions(forbid_remote_imports: true)
+        var awcDiagnostics = AwcDiagnostics()
+        let handle = awc_config_handle_load_with_options(configPath, &options, &awcDiagnostics)
+        defer {
+            awc_config_diagnostics_free(&awcDiagnostics)
+        }
+
+        XCTAssertNil(handle)
+        XCTAssertEqual(awcDiagnostics.number_of_diagnostics, 1)
+        XCTAssertTrue(
+            String(cString: awcDiagnostics.diagnostics[0].message)
+                .contains("remote import https://prelude.dhall-lang.org/v20.2.0/package.dhall is forbidden")
+        )
+    }
+
+    func testCachedConfigInvalidatedByImport() throws {
+        let dir = FileManager.default.temporaryDirectory
+            .appendingPathComponent("awc-cache-test-\(getpid())")
+        try FileManager.default.createDirectory(at: dir, withIntermediateDirectories: true)
+        defer {
+            try? FileManager.default.removeItem(at: dir)
+        }
+        let configPath = dir.appendingPathComponent("config.dhall")
+        let workspacesPath = dir.appendingPathComponent("workspaces.dhall")
+        try "let Types = env:AWC_TYPES in Types.Config::{ workspaces = ./workspaces.dhall }"
+            .write(to: configPath, atomically: true, encoding: .utf8)
+
+        func loadWorkspaces(_ workspaces: String) throws -> Int {
+            try workspaces.write(to: workspacesPath, atomically: true, encoding: .utf8)
+            let handle = try XCTUnwrap(awc_config_handle_load(configPath.path, nil))
+            defer {
+                awc_config_handle_free(handle)
+            }
+            return awc_config_handle_get(handle)!.pointee.number_of_workspaces
+        }
+
+        XCTAssertEqual(try loadWorkspaces("[ \"1\" ]"), 1)
+        XCTAssertEqual(try loadWorkspaces("[ \"1\" ]"), 1)
+        XCTAssertEqual(try loadWorkspaces("[ \"1\", \"2\" ]"), 2)
+    }
+
+    func testDiff() throws {
+        func load(_ name: String) throws -> OpaquePointer {
+            let configPath = Bundle.module.path(forResource: name, ofType: "dhall", inDirectory: "Fixtures")!
+            return try XCTUnwrap(awc_config_handle_load(configPath, nil))
+        }
+        let old = try load("diff_old")
+        let new = try load("diff_new")
+        defer {
+            awc_config_handle_free(old)
+            awc_config_handle_free(new)
+        }
+
+        var diff = AwcConfigDiff()
+        awc_config_diff(old, new, &diff)
+        defer {
+            awc_config_diff_free(&diff)
+        }
+
+        XCTAssertEqual(Array(UnsafeBufferPointer(start: diff.added_gesture_bindings, count: diff.number_of_added_gesture_bindings)), [1])
+        XCTAssertEqual(Array(UnsafeBufferPointer(start: diff.removed_gesture_bindings, count: diff.number_of_removed_gesture_bindings)), [1])
+        XCTAssertEqual(Array(UnsafeBufferPointer(start: diff.added_key_bindings, count: diff.number_of_added_key_bindings)), [1])
+        XCTAssertEqual(Array(UnsafeBufferPointer(start: diff.removed_key_bindings, count: diff.number_of_removed_key_bindings)), [1])
+        XCTAssertEqual(Array(UnsafeBufferPointer(start: diff.added_outputs, count: diff.number_of_added_outputs)), [1])
+        XCTAssertEqual(Array(UnsafeBufferPointer(start: diff.removed_outputs, count: diff.number_of_removed_outputs)), [1])
+        XCTAssertEqual(Array(UnsafeBufferPointer(start: diff.changed_outputs, count: diff.number_of_changed_outputs)), [0])
+        XCTAssertEqual(Array(UnsafeBufferPointer(start: diff.added_workspaces, count: diff.number_of_added_workspaces)), [3])
+        XCTAssertEqual(diff.number_of_removed_workspaces, 0)
+        XCTAssertEqual(diff.number_of_renamed_workspaces, 1)
+        XCTAssertEqual(diff.renamed_workspaces[0].old, 1)
+        XCTAssertEqual(diff.renamed_workspaces[0].new, 1)
+        XCTAssertTrue(diff.border_width_changed)
+        XCTAssertFalse(diff.colors_changed)
+        XCTAssertFalse(diff.layout_changed)
+    }
+
+    func testWindowRules() {
+        withConfig(Bundle.module, "window_rules") {
+            XCTAssertEqual($0.number_of_window_rules, 2)
+
+            let workspaceRule = $0.window_rules[0]
+            XCTAssertEqual(workspaceRule.number_of_matchers, 1)
+            XCTAssertEqual(workspaceRule.matchers[0].property, AwcWindowProperty_AppId)
+            XCTAssertEqual(workspaceRule.matchers[0].kind, AwcPatternKind_Exact)
+            XCTAssertEqual(String(cString: workspaceRule.matchers[0].pattern), "firefox")
+            XCTAssertEqual(String(cString: workspaceRule.workspace), "web")
+            XCTAssertFalse(workspaceRule.float)
+
+            let floatRule = $0.window_rules[1]
+            XCTAssertEqual(floatRule.number_of_matchers, 2)
+            XCTAssertEqual(floatRule.matchers[1].property, AwcWindowProperty_Class)
+            XCTAssertEqual(floatRule.matchers[1].kind, AwcPatternKind_Regex)
+            XCTAssertNil(floatRule.workspace)
+            XCTAssertTrue(floatRule.float)
+            XCTAssertFalse(floatRule.sink)
+        }
+    }
+
+    func testOutputs() {
+        withConfig(Bundle.module, "outputs") {
+            XCTAssertEqual($0.number_of_outputs, 5)
+
+            let first = $0.outputs[0]
+            XCTAssertEqual(first.scale, 1.0)
+            XCTAssertEqual(first.mode.width, 2560)
+            XCTAssertEqual(first.mode.height, 1440)
+            XCTAssertEqual(first.mode.refresh, 143998)
+            XCTAssertEqual(first.transform, AwcTransform_Normal)
+            XCTAssertTrue(first.enabled)
+            XCTAssertTrue(first.adaptive_sync)
+
+            let second = $0.outputs[1]
+            XCTAssertEqual(second.x, 2560)
+            XCTAssertEqual(second.mode.refresh, 0)
+            XCTAssertEqual(second.transform, AwcTransform_Rotate90)
+
+            let third = $0.outputs[2]
+            XCTAssertEqual(third.mode.width, 0)
+            XCTAssertFalse(third.enabled)
+            XCTAssertFalse(third.adaptive_sync)
+
+            let simple = $0.outputs[4]
+            XCTAssertEqual(simple.y, 1440)
+            XCTAssertEqual(simple.scale, 1.5)
+            XCTAssertEqual(simple.mode.width, 0)
+            XCTAssertEqual(simple.transform, AwcTransform_Normal)
+            XCTAssertTrue(simple.enabled)
+        }
+    }
+
+    func testOutputMatching() throws {
+        let configPath = Bundle.module.path(forResource: "output_matching", ofType: "dhall", inDirectory: "Fixtures")!
+        let handle = try XCTUnwrap(awc_config_handle_load(configPath, nil))
+        defer {
+            awc_config_handle_free(handle)
+        }
+
+        func find(
+            _ name: String,
+            make: String = "",
+            model: String = "",
+            serial: String = "",
+            description: String = ""
+        ) throws -> AwcOutputConfig {
+            let strings = [name, make, model, serial, description].map { strdup($0)! }
+            defer {
+                strings.forEach { free($0) }
+            }
+            var info = AwcOutputInfo(
+                name: strings[0], make: strings[1], model: strings[2], serial: strings[3], description: strings[4]
+            )
+            return try XCTUnwrap(awc_config_handle_find_output(handle, &info)).pointee
+        }
+
+        XCTAssertEqual(try find("eDP-1").scale, 1.0)
+        XCTAssertEqual(try find("DP-1").scale, 1.5)
+        XCTAssertEqual(try find("DP-3").scale, 3.0)
+        XCTAssertEqual(try find("DP-2", make: "Dell Inc.", model: "DELL U2719D").scale, 2.0)
+        let serialMatch = try find("DP-2", make: "Dell Inc.", model: "DELL U2719D", serial: "ABC123")
+        XCTAssertEqual(serialMatch.transform, AwcTransform_Rotate90)
+        XCTAssertEqual(serialMatch.scale, 1.0)
+        XCTAssertFalse(try find("HDMI-1", description: "Philips 272E (HDMI-1)").enabled)
+    }
+
+    func testProfiles() throws {
+        let configPath = Bundle.module.path(forResource: "profiles", ofType: "dhall", inDirectory: "Fixtures")!
+        let handle = try XCTUnwrap(awc_config_handle_load(configPath, nil))
+        defer {
+            awc_config_handle_free(handle)
+        }
+
+        func find(_ outputs: [(String, String)]) -> (String, [AwcOutputConfig])? {
+            let strings = outputs.map { (strdup($0.0)!, strdup($0.1)!) }
+            defer {
+                strings.forEach {
+                    free($0.0)
+                    free($0.1)
+                }
+            }
+            let infos = strings.map {
+                AwcOutputInfo(name: $0.0, make: nil, model: nil, serial: $0.1, description: nil)
+            }
+            var configs = [UnsafePointer<AwcOutputConfig>?](repeating: nil, count: outputs.count)
+            guard let profile = awc_config_handle_find_profile(handle, infos, infos.count, &configs) else {
+                return nil
+            }
+            return (String(cString: profile.pointee.name), configs.map { $0!.pointee })
+        }
+
+        let (laptop, laptopConfigs) = try XCTUnwrap(find([("eDP-1", "")]))
+        XCTAssertEqual(laptop, "laptop")
+        XCTAssertEqual(laptopConfigs[0].scale, 2.0)
+
+        let (office, officeConfigs) = try XCTUnwrap(find([("eDP-1", ""), ("DP-1", ""), ("DP-2", "ABC123")]))
+        XCTAssertEqual(office, "office")
+        XCTAssertFalse(officeConfigs[0].enabled)
+        XCTAssertEqual(officeConfigs[1].x, 0)
+        XCTAssertEqual(officeConfigs[2].x, 2560)
+        XCTAssertEqual(officeConfigs[2].transform, AwcTransform_Rotate90)
+
+        XCTAssertNil(find([("eDP-1", ""), ("DP-1", "")]))
+    }
+
+    func testKeyboards() throws {
+        let configPath = Bundle.module.path(forResource: "keyboards", ofType: "dhall", inDirectory: "Fixtures")!
+        let handle = try XCTUnwrap(awc_config_handle_load(configPath, nil))
+        defer {
+            awc_config_handle_free(handle)
+        }
+
+        let kinesis = try XCTUnwrap(awc_config_handle_find_keyboard(handle, "Kinesis Advantage2 Keyboard", External))
+        XCTAssertEqual(String(cString: kinesis.pointee.layout), "us")
+        XCTAssertEqual(String(cString: kinesis.pointee.variant), "altgr-intl")
+        XCTAssertEqual(String(cString: kinesis.pointee.options), "ctrl:nocaps,grp:alt_shift_toggle")
+        XCTAssertEqual(kinesis.pointee.repeat_rate, 40)
+        XCTAssertEqual(kinesis.pointee.repeat_delay, 250)
+        XCTAssertTrue(kinesis.pointee.numlock)
+
+        let builtin = try XCTUnwrap(awc_config_handle_find_keyboard(handle, "AT Translated Set 2 keyboard", Builtin))
+        XCTAssertEqual(String(cString: builtin.pointee.layout), "de")
+        XCTAssertEqual(String(cString: builtin.pointee.model), "pc105")
+        XCTAssertEqual(String(cString: builtin.pointee.options), "compose:rctrl")
+        XCTAssertEqual(builtin.pointee.repeat_rate, 25)
+        XCTAssertFalse(builtin.pointee.numlock)
+
+        let external = try XCTUnwrap(awc_config_handle_find_keyboard(handle, "Some Keyboard", External))
+        XCTAssertEqual(String(cString: external.pointee.layout), "fr")
+        XCTAssertEqual(String(cString: external.pointee.variant), "")
+        XCTAssertEqual(String(cString: external.pointee.options), "compose:rctrl")
+        XCTAssertEqual(external.pointee.repeat_delay, 600)
+    }
+
+    func testInputs() throws {
+        let configPath = Bundle.module.path(forResource: "inputs", ofType: "dhall", inDirectory: "Fixtures")!
+        let handle = try XCTUnwrap(awc_config_handle_load(configPath, nil))
+        defer {
+            awc_config_handle_free(handle)
+        }
+
+        let config = awc_config_handle_get(handle)!.pointee
+        XCTAssertEqual(config.number_of_inputs, 3)
+        XCTAssertNil(config.inputs[0].matches[0].name)
+        XCTAssertEqual(config.inputs[0].matches[0].type_, AwcInputType_Touchpad)
+        XCTAssertEqual(String(cString: config.inputs[1].matches[0].name), "Logitech*")
+
+        var mouse = AwcInputConfig()
+        XCTAssertTrue(awc_config_handle_find_input(handle, "Logitech G Pro", AwcInputType_Pointer, &mouse))
+        XCTAssertEqual(mouse.accel_profile, AwcAccelProfile_Flat)
+        XCTAssertTrue(mouse.has_accel_speed)
+        XCTAssertEqual(mouse.accel_speed, -0.5)
+        XCTAssertEqual(mouse.tap, AwcToggle_Default)
+
+        var touchpad = AwcInputConfig()
+        XCTAssertTrue(
+            awc_config_handle_find_input(handle, "SynPS/2 Synaptics TouchPad", AwcInputType_Touchpad, &touchpad))
+        XCTAssertEqual(touchpad.tap, AwcToggle_Enabled)
+        XCTAssertEqual(touchpad.natural_scroll, AwcToggle_Disabled)
+        XCTAssertEqual(touchpad.scroll_method, AwcScrollMethod_TwoFinger)
+        XCTAssertEqual(touchpad.disable_while_typing, AwcToggle_Enabled)
+        XCTAssertFalse(touchpad.has_accel_speed)
+
+        var other = AwcInputConfig()
+        XCTAssertFalse(awc_config_handle_find_input(handle, "Some Mouse", AwcInputType_Pointer, &other))
+    }
+
+    func testWorkspaces() {
+        withConfig(Bundle.module, "workspaces") {
+            XCTAssertEqual($0.number_of_workspaces, 3)
+
+            let plain = $0.workspaces[0]
+            XCTAssertEqual(String(cString: plain.tag), "1")
+            XCTAssertEqual(String(cString: plain.name), "1")
+            XCTAssertNil(plain.layout)
+            XCTAssertEqual(plain.output, 0)
+
+            let web = $0.workspaces[2]
+            XCTAssertEqual(String(cString: web.tag), "web")
+            XCTAssertEqual(String(cString: web.name), "www")
+            XCTAssertEqual(web.number_of_layout_ops, 1)
+            XCTAssertEqual(web.output, 2)
+        }
+    }
+
+    func testModes() {
+        withConfig(Bundle.module, "modes") {
+            XCTAssertEqual(String(cString: $0.key_bindings[0].action.enter_mode), "resize")
+
+            XCTAssertEqual($0.number_of_modes, 1)
+            let mode = $0.modes[0]
+            XCTAssertEqual(String(cString: mode.name), "resize")
+            XCTAssertEqual(mode.number_of_key_bindings, 3)
+            XCTAssertTrue(mode.key_bindings[2].action.exit_mode)
+        }
+    }
+
+    func testSequence() {
+        withConfig(Bundle.module, "sequence") {
+            let action = $0.key_bindings[0].action
+            XCTAssertEqual(action.number_of_sequence_actions, 2)
+            XCTAssertEqual(String(cString: action.sequence[0].move_to), "3")
+            XCTAssertEqual(String(cString: action.sequence[1].view), "3")
+        }
+    }
+
+    func testDiagnostics() {
+        withDiagnostics(Bundle.module, "invalid") { (loaded, diagnostics) in
+            XCTAssertFalse(loaded)
+            XCTAssertEqual(
+                diagnostics.map { $0.1 },
+                [
+                    "workspaces",
+                    "keyboardSettings[0]",
+                    "outputs[0].scale",
+                    "keyBindings[0].action",
+                    "keyBindings[1].action",
+                    "keyBindings[1]",
+                    "keyBindings[2].key",
+                    "buttonBindings[0].action",
+                ]
+            )
+            XCTAssertEqual(
+                diagnostics.map { $0.0 },
+                [
+                    AwcSeverity_Error, AwcSeverity_Error, AwcSeverity_Error, AwcSeverity_Warning,
+                    AwcSeverity_Error, AwcSeverity_Warning, AwcSeverity_Error, AwcSeverity_Error,
+                ]
+            )
+        }
+    }
+
+    func testInvalidRegex() {
+        withDiagnostics(Bundle.module, "invalid_regex") { (loaded, diagnostics) in
+            XCTAssertFalse(loaded)
+            XCTAssertEqual(diagnostics.map { $0.1 }, ["windowRules[0].matches[0]"])
+            XCTAssertEqual(diagnostics.map { $0.0 }, [AwcSeverity_Error])
+        }
+    }
+
+    func testWarnings() {
+        withDiagnostics(Bundle.module, "warnings") { (loaded, diagnostics) in
+            XCTAssertTrue(loaded)
+            XCTAssertEqual(diagnostics.map { $0.1 }, ["keyBindings[0].action", "keyBindings[1].action"])
+            XCTAssertEqual(diagnostics.map { $0.0 }, [AwcSeverity_Warning, AwcSeverity_Warning])
+        }
+    }
+
+    func testColors() {
+        withConfig(Bundle.module, "colors") {
+            let active = $0.colors.borders.active
+            XCTAssertEqual([active.r, active.g, active.b, active.a], [0xe3, 0xc5, 0x98, 0xff])
+            let inactive = $0.colors.borders.inactive
+            XCTAssertEqual([inactive.r, inactive.g, inactive.b, inactive.a], [0x80, 0x80, 0x80, 0xff])
+            let resizeFrame = $0.colors.resize_frame
+            XCTAssertEqual([resizeFrame.r, resizeFrame.g, resizeFrame.b, resizeFrame.a], [0x18, 0xca, 0xe6, 0x80])
+            let tabs = $0.tabs
+            XCTAssertEqual(
+                [tabs.active_background.r, tabs.active_background.g, tabs.active_background.b],
+                [0xff, 0x00, 0x00])
+            XCTAssertEqual(tabs.height, 30)
+        }
+    }
+
+    func testInvalidColor() {
+        withDiagnostics(Bundle.module, "invalid_color") { (loaded, diagnostics) in
+            XCTAssertFalse(loaded)
+            XCTAssertEqual(diagnostics.count, 1)
+            XCTAssertTrue(diagnostics[0].2.contains("invalid color `#18cae`"), diagnostics[0].2)
+        }
+    }
+
+    func testInvalidLayout() {
+        withDiagnostics(Bundle.module, "invalid_layout") { (loaded, diagnostics) in
+            XCTAssertFalse(loaded)
+            XCTAssertEqual(diagnostics.map { $0.1 }, ["layout[0]", "layout[1]", "layout[3]", "layout[4]"])
+            XCTAssertTrue(diagnostics[0].2.contains("at least one column"), diagnostics[0].2)
+            XCTAssertTrue(diagnostics[1].2.contains("greater than 0"), diagnostics[1].2)
+            XCTAssertTrue(diagnostics[2].2.contains("between 0 and 1"), diagnostics[2].2)
+            XCTAssertTrue(diagnostics[3].2.contains("must not be empty"), diagnostics[3].2)
+        }
+    }
+
+    func testDeprecatedLoad() {
+        let configPath = Bundle.module.path(forResource: "window_rules", ofType: "dhall", inDirectory: "Fixtures")!
+        var awcConfig = AwcConfig()
+        XCTAssertNil(awc_config_load(configPath, &awcConfig))
+        XCTAssertEqual(awcConfig.number_of_window_rules, 2)
+        XCTAssertEqual(String(cString: awcConfig.window_rules[0].workspace), "web")
+        awc_config_free(&awcConfig)
+    }
+
     public static var allTests = [
         ("testEmptyConfig", testEmptyConfig),
         ("testButtonBinding", testButtonBinding),
+        ("testButtonActions", testButtonActions),
+        ("testGestureBindings", testGestureBindings),
+        ("testRelativeImport", testRelativeImport),
+        ("testForbiddenRemoteImport", testForbiddenRemoteImport),
+        ("testCachedConfigInvalidatedByImport", testCachedConfigInvalidatedByImport),
+        ("testDiff", testDiff),
+        ("testWindowRules", testWindowRules),
+        ("testOutputs", testOutputs),
+        ("testOutputMatching", testOutputMatching),
+        ("testProfiles", testProfiles),
+        ("testKeyboards", testKeyboards),
+        ("testInputs", testInputs),
+        ("testWorkspaces", testWorkspaces),
+        ("testModes", testModes),
+        ("testSequence", testSequence),
+        ("testDiagnostics", testDiagnostics),
+        ("testInvalidRegex", testInvalidRegex),
+        ("testWarnings", testWarnings),
+        ("testColors", testColors),
+        ("testInvalidColor", testInvalidColor),
+        ("testInvalidLayout", testInvalidLayout),
+        ("testDeprecatedLoad", testDeprecatedLoad),
     ]
 }
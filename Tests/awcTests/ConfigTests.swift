@@ -42,4 +42,90 @@ public final class ConfigTests: XCTestCase {
             XCTAssertTrue(wrapped! is Capped<Tiled<TestView, ()>>)
         }
     }
+
+    func testBuildSpacingLayout() {
+        withConfig(Bundle.module, "spacing") {
+            let layout: AnyLayout<TestView, ()> = try! buildLayout($0.layout, $0.number_of_layout_ops)
+
+            let wrapped = Mirror(reflecting: layout).descendant("wrapped")
+            XCTAssertTrue(wrapped! is Spacing<Tiled<TestView, ()>>)
+            XCTAssertEqual(layout.description, "Spacing(Tiled, 10, 5)")
+        }
+    }
+
+    func testBuildGridLayout() {
+        withConfig(Bundle.module, "grid") {
+            let views = (0..<4).map { TestView(id: $0) }
+            let stack = Stack(up: List.empty, focus: views[0], down: List(sequence: Array(views[1...])))
+            let box = wlr_box(x: 0, y: 0, width: 300, height: 200)
+
+            let layout: AnyLayout<TestView, ()> = try! buildLayout($0.layout, $0.number_of_layout_ops)
+
+            let wrapped = Mirror(reflecting: layout).descendant("wrapped")
+            XCTAssertTrue(wrapped! is Grid<TestView, ()>)
+
+            let workspace = Workspace(tag: "test", layout: layout)
+            let output = Output(data: (), workspace: workspace)
+            let arrangement = layout.doLayout(dataProvider: NoDataProvider(), output: output, stack: stack, box: box)
+            XCTAssertEqual(
+                arrangement.map { $0.2 },
+                [
+                    wlr_box(x: 0, y: 0, width: 100, height: 100),
+                    wlr_box(x: 100, y: 0, width: 100, height: 100),
+                    wlr_box(x: 200, y: 0, width: 100, height: 100),
+                    wlr_box(x: 0, y: 100, width: 300, height: 100),
+                ])
+        }
+    }
+
+    func testBuildThreeColumnLayout() {
+        withConfig(Bundle.module, "three_column") {
+            let layout: AnyLayout<TestView, ()> = try! buildLayout($0.layout, $0.number_of_layout_ops)
+
+            let wrapped = Mirror(reflecting: layout).descendant("wrapped")
+            XCTAssertTrue(wrapped! is Reflected<Capped<ThreeColumn<TestView, ()>>>)
+        }
+    }
+
+    func testBuildSpiralLayout() {
+        withConfig(Bundle.module, "spiral") {
+            let layout: AnyLayout<TestView, ()> = try! buildLayout($0.layout, $0.number_of_layout_ops)
+
+            let wrapped = Mirror(reflecting: layout).descendant("wrapped")
+            XCTAssertTrue(wrapped! is Spiral<TestView, ()>)
+            XCTAssertEqual(layout.description, "Spiral")
+        }
+    }
+
+    func testBuildTabbedLayout() {
+        withConfig(Bundle.module, "tabbed") {
+            let view = TestView()
+            let stack = Stack(up: List.empty, focus: view, down: List(sequence: [TestView(id: 1)]))
+            let box = wlr_box(x: 0, y: 0, width: 1024, height: 768)
+
+            let layout: AnyLayout<TestView, ()> = try! buildLayout($0.layout, $0.number_of_layout_ops)
+
+            let wrapped = Mirror(reflecting: layout).descendant("wrapped")
+            XCTAssertTrue(wrapped! is Tabbed<TestView, ()>)
+
+            let workspace = Workspace(tag: "test", layout: layout)
+            let output = Output(data: (), workspace: workspace)
+            let arrangement = layout.doLayout(dataProvider: NoDataProvider(), output: output, stack: stack, box: box)
+            XCTAssertEqual(arrangement.count, 1)
+            XCTAssertEqual(arrangement[0].0, view)
+            XCTAssertEqual(arrangement[0].1, [.focused, .tabbed])
+            XCTAssertEqual(arrangement[0].2, box)
+        }
+    }
+
+    func testBuildNamedLayout() {
+        withConfig(Bundle.module, "named") {
+            let layout: AnyLayout<TestView, ()> = try! buildLayout($0.layout, $0.number_of_layout_ops)
+
+            let wrapped = Mirror(reflecting: layout).descendant("wrapped")
+            XCTAssertTrue(wrapped! is Choose<Named<Capped<Tiled<TestView, ()>>>, Named<Tabbed<TestView, ()>>>)
+            XCTAssertEqual(layout.description, "Two pane")
+            XCTAssertEqual(layout.nextLayout()?.description, "Tabs")
+        }
+    }
 }
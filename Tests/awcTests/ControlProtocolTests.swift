@@ -47,4 +47,14 @@ public final class CtlProtocolDecoderTests: XCTestCase {
         XCTAssertEqual(2, requests.count)
         XCTAssertEqual(CtlRequest.setLayout(1), requests[1])
     }
+
+    func testDecodesSetLayoutByName() {
+        let decoder = CtlProtocolDecoder()
+
+        let requests = "\u{22}\0\0\0{\"cmd\":\"set_layout\",\"name\":\"Wide\"}".withCString() {
+            try! decoder.pushBytes(bytes: $0, count: 38)
+        }
+        XCTAssertEqual(1, requests.count)
+        XCTAssertEqual(CtlRequest.setLayoutByName("Wide"), requests[0])
+    }
 }
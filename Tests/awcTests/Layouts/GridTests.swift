@@ -0,0 +1,70 @@
+import XCTest
+
+import Libawc
+import Wlroots
+import testHelpers
+
+fileprivate typealias Arrangement = [(TestView, Set<ViewAttribute>, wlr_box)]
+
+public final class GridTests: XCTestCase {
+    private let output = Output(data: (), workspace: Workspace(tag: "test", layout: TestLayout()))
+    private let box = wlr_box(x: 0, y: 0, width: 300, height: 200)
+
+    func testSingleViewTakesFullSpace() {
+        let view = TestView()
+        let layout = Grid<TestView, ()>()
+
+        let result = layout.doLayout(
+            dataProvider: NoDataProvider(), output: output, stack: Stack.singleton(view), box: box)
+
+        let expected: Arrangement = [(view, [.focused], box)]
+        XCTAssertEqual(
+            expected.map(ArrangementEqWrapper.init),
+            result.map(ArrangementEqWrapper.init))
+    }
+
+    func testAutoLastRowSharesWidth() {
+        let views = (0..<5).map { TestView(id: $0) }
+        let stack = Stack(up: List.empty, focus: views[0], down: List(sequence: Array(views[1...])))
+        let layout = Grid<TestView, ()>(shape: .auto)
+
+        let result = layout.doLayout(dataProvider: NoDataProvider(), output: output, stack: stack, box: box)
+
+        let expected: Arrangement = [
+            (views[0], [.focused], wlr_box(x: 0, y: 0, width: 100, height: 100)),
+            (views[1], [], wlr_box(x: 100, y: 0, width: 100, height: 100)),
+            (views[2], [], wlr_box(x: 200, y: 0, width: 100, height: 100)),
+            (views[3], [], wlr_box(x: 0, y: 100, width: 150, height: 100)),
+            (views[4], [], wlr_box(x: 150, y: 100, width: 150, height: 100)),
+        ]
+        XCTAssertEqual(
+            expected.map(ArrangementEqWrapper.init),
+            result.map(ArrangementEqWrapper.init))
+    }
+
+    func testFixedColumns() {
+        let views = (0..<3).map { TestView(id: $0) }
+        let stack = Stack(up: List.empty, focus: views[0], down: List(sequence: Array(views[1...])))
+        let layout = Grid<TestView, ()>(shape: .columns(1))
+
+        let result = layout.doLayout(dataProvider: NoDataProvider(), output: output, stack: stack, box: box)
+
+        XCTAssertEqual(result.map { $0.2.width }, [300, 300, 300])
+        XCTAssertEqual(result.map { $0.2.y }, [0, 66, 133])
+    }
+
+    func testAspectRatio() {
+        let views = (0..<4).map { TestView(id: $0) }
+        let stack = Stack(up: List.empty, focus: views[0], down: List(sequence: Array(views[1...])))
+        // Wide cells on a tall output result in a single column
+        let layout = Grid<TestView, ()>(shape: .aspectRatio(4))
+
+        let result = layout.doLayout(
+            dataProvider: NoDataProvider(),
+            output: output,
+            stack: stack,
+            box: wlr_box(x: 0, y: 0, width: 200, height: 400))
+
+        XCTAssertEqual(result.map { $0.2.width }, [200, 200, 200, 200])
+    }
+}
@@ -0,0 +1,49 @@
+import XCTest
+
+import Libawc
+import Wlroots
+import testHelpers
+
+fileprivate typealias Arrangement = [(TestView, Set<ViewAttribute>, wlr_box)]
+
+public final class SpacingTests: XCTestCase {
+    private let output = Output(data: (), workspace: Workspace(tag: "test", layout: TestLayout()))
+    private let mainView = TestView(id: 0)
+    private let secondView = TestView(id: 1)
+    private let box = wlr_box(x: 0, y: 0, width: 200, height: 100)
+
+    func testGapsBetweenViewsAndAtEdges() {
+        let stack = Stack(up: List.empty, focus: mainView, down: List(sequence: [secondView]))
+        let layout = Spacing(layout: Tiled<TestView, ()>(split: 0.5, delta: 0.1), inner: 10, outer: 4, smartGaps: false)
+
+        let result = layout.doLayout(dataProvider: NoDataProvider(), output: output, stack: stack, box: box)
+
+        let expected: Arrangement = [
+            (mainView, [.focused], wlr_box(x: 4, y: 4, width: 91, height: 92)),
+            (secondView, [], wlr_box(x: 105, y: 4, width: 91, height: 92)),
+        ]
+        XCTAssertEqual(
+            expected.map(ArrangementEqWrapper.init),
+            result.map(ArrangementEqWrapper.init))
+    }
+
+    func testSmartGapsOmitGapsForSingleView() {
+        let stack = Stack.singleton(mainView)
+        let layout = Spacing(layout: Tiled<TestView, ()>(split: 0.5, delta: 0.1), inner: 10, outer: 4, smartGaps: true)
+
+        let result = layout.doLayout(dataProvider: NoDataProvider(), output: output, stack: stack, box: box)
+
+        XCTAssertEqual(result.count, 1)
+        XCTAssertEqual(result[0].2, box)
+    }
+
+    func testGapsForSingleViewWithoutSmartGaps() {
+        let stack = Stack.singleton(mainView)
+        let layout = Spacing(layout: Tiled<TestView, ()>(split: 0.5, delta: 0.1), inner: 10, outer: 4, smartGaps: false)
+
+        let result = layout.doLayout(dataProvider: NoDataProvider(), output: output, stack: stack, box: box)
+
+        XCTAssertEqual(result.count, 1)
+        XCTAssertEqual(result[0].2, wlr_box(x: 4, y: 4, width: 192, height: 92))
+    }
+}
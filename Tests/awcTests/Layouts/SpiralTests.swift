@@ -0,0 +1,44 @@
+import XCTest
+
+import Libawc
+import Wlroots
+import testHelpers
+
+fileprivate typealias Arrangement = [(TestView, Set<ViewAttribute>, wlr_box)]
+
+public final class SpiralTests: XCTestCase {
+    private let output = Output(data: (), workspace: Workspace(tag: "test", layout: TestLayout()))
+    private let box = wlr_box(x: 0, y: 0, width: 400, height: 200)
+
+    func testSingleViewTakesFullSpace() {
+        let view = TestView()
+        let layout = Spiral<TestView, ()>(ratio: 0.5)
+
+        let result = layout.doLayout(
+            dataProvider: NoDataProvider(), output: output, stack: Stack.singleton(view), box: box)
+
+        let expected: Arrangement = [(view, [.focused], box)]
+        XCTAssertEqual(
+            expected.map(ArrangementEqWrapper.init),
+            result.map(ArrangementEqWrapper.init))
+    }
+
+    func testViewsFormSpiral() {
+        let views = (0..<5).map { TestView(id: $0) }
+        let stack = Stack(up: List.empty, focus: views[0], down: List(sequence: Array(views[1...])))
+        let layout = Spiral<TestView, ()>(ratio: 0.5)
+
+        let result = layout.doLayout(dataProvider: NoDataProvider(), output: output, stack: stack, box: box)
+
+        let expected: Arrangement = [
+            (views[0], [.focused], wlr_box(x: 0, y: 0, width: 200, height: 200)),
+            (views[1], [], wlr_box(x: 200, y: 0, width: 200, height: 100)),
+            (views[2], [], wlr_box(x: 300, y: 100, width: 100, height: 100)),
+            (views[3], [], wlr_box(x: 200, y: 150, width: 100, height: 50)),
+            (views[4], [], wlr_box(x: 200, y: 100, width: 100, height: 50)),
+        ]
+        XCTAssertEqual(
+            expected.map(ArrangementEqWrapper.init),
+            result.map(ArrangementEqWrapper.init))
+    }
+}
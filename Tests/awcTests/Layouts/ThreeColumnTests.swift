@@ -0,0 +1,62 @@
+import XCTest
+
+import Libawc
+import Wlroots
+import testHelpers
+
+fileprivate typealias Arrangement = [(TestView, Set<ViewAttribute>, wlr_box)]
+
+public final class ThreeColumnTests: XCTestCase {
+    private let output = Output(data: (), workspace: Workspace(tag: "test", layout: TestLayout()))
+    private let views = (0..<4).map { TestView(id: $0) }
+    private let box = wlr_box(x: 0, y: 0, width: 300, height: 100)
+
+    func testCentered() {
+        let stack = Stack(up: List.empty, focus: views[0], down: List(sequence: Array(views[1...])))
+        let layout = ThreeColumn<TestView, ()>(split: 0.5, delta: 0.1, centered: true)
+
+        let result = layout.doLayout(dataProvider: NoDataProvider(), output: output, stack: stack, box: box)
+
+        let expected: Arrangement = [
+            (views[0], [.focused], wlr_box(x: 75, y: 0, width: 150, height: 100)),
+            (views[1], [], wlr_box(x: 225, y: 0, width: 75, height: 50)),
+            (views[2], [], wlr_box(x: 225, y: 50, width: 75, height: 50)),
+            (views[3], [], wlr_box(x: 0, y: 0, width: 75, height: 100)),
+        ]
+        XCTAssertEqual(
+            expected.map(ArrangementEqWrapper.init),
+            result.map(ArrangementEqWrapper.init))
+    }
+
+    func testNotCentered() {
+        let stack = Stack(up: List.empty, focus: views[0], down: List(sequence: Array(views[1...])))
+        let layout = ThreeColumn<TestView, ()>(split: 0.5, delta: 0.1, centered: false)
+
+        let result = layout.doLayout(dataProvider: NoDataProvider(), output: output, stack: stack, box: box)
+
+        let expected: Arrangement = [
+            (views[0], [.focused], wlr_box(x: 0, y: 0, width: 150, height: 100)),
+            (views[1], [], wlr_box(x: 150, y: 0, width: 75, height: 50)),
+            (views[2], [], wlr_box(x: 150, y: 50, width: 75, height: 50)),
+            (views[3], [], wlr_box(x: 225, y: 0, width: 75, height: 100)),
+        ]
+        XCTAssertEqual(
+            expected.map(ArrangementEqWrapper.init),
+            result.map(ArrangementEqWrapper.init))
+    }
+
+    func testTwoViewsLikeTiled() {
+        let stack = Stack(up: List.empty, focus: views[0], down: List(sequence: [views[1]]))
+        let layout = ThreeColumn<TestView, ()>(split: 0.5, delta: 0.1, centered: true)
+
+        let result = layout.doLayout(dataProvider: NoDataProvider(), output: output, stack: stack, box: box)
+
+        let expected: Arrangement = [
+            (views[0], [.focused], wlr_box(x: 0, y: 0, width: 150, height: 100)),
+            (views[1], [], wlr_box(x: 150, y: 0, width: 150, height: 100)),
+        ]
+        XCTAssertEqual(
+            expected.map(ArrangementEqWrapper.init),
+            result.map(ArrangementEqWrapper.init))
+    }
+}
@@ -6,16 +6,44 @@ import awc_config
 public func withConfig(_ bundle: Bundle, _ fixtureName: String, _ block: (AwcConfig) -> ()) {
     let configPath = bundle.path(forResource: fixtureName, ofType: "dhall", inDirectory: "Fixtures")!
 
-    var awcConfig = AwcConfig()
-    if let error = awc_config_load(configPath, &awcConfig) {
-        defer {
-            awc_config_str_free(error)
-        }
-        XCTFail(String(cString: error))
+    var awcDiagnostics = AwcDiagnostics()
+    let handle = awc_config_handle_load(configPath, &awcDiagnostics)
+    defer {
+        awc_config_diagnostics_free(&awcDiagnostics)
+        awc_config_handle_free(handle)
+    }
+
+    if let handle = handle {
+        block(awc_config_handle_get(handle)!.pointee)
     } else {
-        defer {
-            awc_config_free(&awcConfig)
+        for i in 0..<awcDiagnostics.number_of_diagnostics {
+            XCTFail(String(cString: awcDiagnostics.diagnostics[i].message))
         }
-        block(awcConfig)
     }
 }
+
+public func withDiagnostics(
+    _ bundle: Bundle,
+    _ fixtureName: String,
+    _ block: (Bool, [(AwcSeverity, String, String)]) -> ()
+) {
+    let configPath = bundle.path(forResource: fixtureName, ofType: "dhall", inDirectory: "Fixtures")!
+
+    var awcDiagnostics = AwcDiagnostics()
+    let handle = awc_config_handle_load(configPath, &awcDiagnostics)
+    defer {
+        awc_config_diagnostics_free(&awcDiagnostics)
+        awc_config_handle_free(handle)
+    }
+
+    var diagnostics: [(AwcSeverity, String, String)] = []
+    for i in 0..<awcDiagnostics.number_of_diagnostics {
+        let diagnostic = awcDiagnostics.diagnostics[i]
+        diagnostics.append((
+            diagnostic.severity,
+            String(cString: diagnostic.path),
+            String(cString: diagnostic.message)
+        ))
+    }
+    block(handle != nil, diagnostics)
+}
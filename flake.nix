@@ -39,6 +39,7 @@
           wlroots
           libdrm
           libxkbcommon
+          libinput
           udev
           libevdev
           pixman